@@ -0,0 +1,46 @@
+//! Benchmarks the assembly of RPC blocks from their header and transactions,
+//! with hashes only or full transactions, for blocks of 0, 10 and 100 transactions.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use kakarot_rpc::models::block::rpc_block;
+use reth_primitives::B256;
+use reth_rpc_types::{BlockTransactions, Header, Transaction};
+
+fn header() -> Header {
+    Header { hash: Some(B256::repeat_byte(1)), number: Some(1), ..Default::default() }
+}
+
+fn transactions(count: usize, full: bool) -> BlockTransactions {
+    let hashes = (0..count).map(|i| B256::left_padding_from(&i.to_be_bytes()));
+    if full {
+        BlockTransactions::Full(
+            hashes
+                .enumerate()
+                .map(|(i, hash)| Transaction {
+                    hash,
+                    block_hash: Some(B256::repeat_byte(1)),
+                    block_number: Some(1),
+                    transaction_index: Some(i as u64),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    } else {
+        BlockTransactions::Hashes(hashes.collect())
+    }
+}
+
+fn bench_rpc_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rpc_block");
+    for count in [0, 10, 100] {
+        for (name, full) in [("hashes", false), ("full", true)] {
+            let transactions = transactions(count, full);
+            group.bench_with_input(BenchmarkId::new(name, count), &transactions, |b, transactions| {
+                b.iter(|| rpc_block(black_box(header()), black_box(transactions.clone())).unwrap());
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_rpc_block);
+criterion_main!(benches);
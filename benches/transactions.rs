@@ -0,0 +1,31 @@
+//! Benchmarks the conversion of the transactions of Starknet blocks into the EVM
+//! transactions they carry, for blocks of 10, 100 and 2000 Kakarot transactions.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use kakarot_rpc::models::transaction::filter_starknet_into_eth_txs;
+use starknet::core::types::Transaction;
+use starknet_crypto::FieldElement;
+
+fn kakarot_address() -> FieldElement {
+    FieldElement::from_hex_be("0x2fcc35a38e2b535ccecf635e67fdb558cb24924e011a5b6851ec7e3f03f8473").unwrap()
+}
+
+fn transactions(count: usize) -> Vec<Transaction> {
+    let transaction: Transaction =
+        serde_json::from_str(include_str!("../src/models/test_data/conversion/starknet/transaction.json"))
+            .expect("Failed to deserialize Starknet transaction");
+    vec![transaction; count]
+}
+
+fn bench_filter_starknet_into_eth_txs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter_starknet_into_eth_txs");
+    for count in [10, 100, 2000] {
+        let transactions = transactions(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &transactions, |b, transactions| {
+            b.iter(|| filter_starknet_into_eth_txs(black_box(transactions), kakarot_address(), false, false));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_filter_starknet_into_eth_txs);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+//! Feeds arbitrary felts to the Kakarot calldata decoder, which should never panic.
+#![no_main]
+
+use kakarot_rpc::models::transaction::decode_kakarot_calldata;
+use libfuzzer_sys::fuzz_target;
+use starknet_crypto::FieldElement;
+
+/// Number of felts preceding the RLP encoded transaction in the Kakarot calldata.
+const PREFIX_LEN: usize = 6;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((mode, data)) = data.split_first() else {
+        return;
+    };
+
+    let felts: Vec<FieldElement> = if mode % 2 == 0 {
+        // Arbitrary felts, built from chunks of 8 bytes
+        data.chunks(8)
+            .map(|chunk| {
+                let mut bytes = [0u8; 8];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                FieldElement::from(u64::from_be_bytes(bytes))
+            })
+            .collect()
+    } else {
+        // Well formed prefix followed by one felt per byte, which reaches the RLP decoding
+        let mut felts = vec![FieldElement::ZERO; PREFIX_LEN - 1];
+        felts.push(FieldElement::from(data.len() as u64));
+        felts.extend(data.iter().map(|byte| FieldElement::from(*byte)));
+        felts
+    };
+
+    let _ = decode_kakarot_calldata(&felts);
+});
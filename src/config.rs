@@ -1,10 +1,18 @@
 use eyre::eyre;
+use lazy_static::lazy_static;
 use starknet::core::types::FieldElement;
+use starknet::core::utils::cairo_short_string_to_felt;
 use starknet::providers::jsonrpc::{HttpTransport, JsonRpcTransport};
-use starknet::providers::{JsonRpcClient, SequencerGatewayProvider};
+use starknet::providers::{JsonRpcClient, Provider, SequencerGatewayProvider};
 use std::env::var;
 use url::Url;
 
+lazy_static! {
+    /// Upper bound of the Starknet contract addresses: 2**251 - 256.
+    static ref STARKNET_ADDRESS_BOUND: FieldElement =
+        FieldElement::from_hex_be("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00").unwrap();
+}
+
 fn env_var_to_field_element(var_name: &str) -> Result<FieldElement, eyre::Error> {
     let env_var = var(var_name)?;
 
@@ -71,6 +79,10 @@ pub struct KakarotRpcConfig {
     pub uninitialized_account_class_hash: FieldElement,
     /// Account contract class hash.
     pub account_contract_class_hash: FieldElement,
+    /// Expected class hash of the Kakarot contract, checked at startup if set.
+    pub kakarot_class_hash: Option<FieldElement>,
+    /// Expected chain id of the Starknet provider, checked at startup if set.
+    pub expected_starknet_chain_id: Option<FieldElement>,
 }
 
 impl KakarotRpcConfig {
@@ -80,7 +92,28 @@ impl KakarotRpcConfig {
         uninitialized_account_class_hash: FieldElement,
         account_contract_class_hash: FieldElement,
     ) -> Self {
-        Self { network, kakarot_address, uninitialized_account_class_hash, account_contract_class_hash }
+        Self {
+            network,
+            kakarot_address,
+            uninitialized_account_class_hash,
+            account_contract_class_hash,
+            kakarot_class_hash: None,
+            expected_starknet_chain_id: None,
+        }
+    }
+
+    /// Sets the expected class hash of the Kakarot contract.
+    #[must_use]
+    pub const fn with_kakarot_class_hash(mut self, kakarot_class_hash: FieldElement) -> Self {
+        self.kakarot_class_hash = Some(kakarot_class_hash);
+        self
+    }
+
+    /// Sets the expected chain id of the Starknet provider.
+    #[must_use]
+    pub const fn with_expected_starknet_chain_id(mut self, chain_id: FieldElement) -> Self {
+        self.expected_starknet_chain_id = Some(chain_id);
+        self
     }
 
     /// Create a new `StarknetConfig` from environment variables.
@@ -93,8 +126,60 @@ impl KakarotRpcConfig {
             kakarot_address: env_var_to_field_element("KAKAROT_ADDRESS")?,
             uninitialized_account_class_hash: env_var_to_field_element("UNINITIALIZED_ACCOUNT_CLASS_HASH")?,
             account_contract_class_hash: env_var_to_field_element("ACCOUNT_CONTRACT_CLASS_HASH")?,
+            kakarot_class_hash: var("KAKAROT_CLASS_HASH")
+                .ok()
+                .filter(|h| !h.is_empty())
+                .map(|h| FieldElement::from_hex_be(&h))
+                .transpose()?,
+            expected_starknet_chain_id: var("EXPECTED_STARKNET_CHAIN_ID")
+                .ok()
+                .filter(|id| !id.is_empty())
+                .map(|id| parse_chain_id(&id))
+                .transpose()?,
         })
     }
+
+    /// Checks that the configuration is usable: the Kakarot address must be a valid nonzero
+    /// Starknet contract address and the class hashes must be set.
+    pub fn validate(&self) -> Result<(), eyre::Error> {
+        if self.kakarot_address == FieldElement::ZERO || self.kakarot_address >= *STARKNET_ADDRESS_BOUND {
+            return Err(eyre!("Invalid Kakarot address {:#x}", self.kakarot_address));
+        }
+        if self.uninitialized_account_class_hash == FieldElement::ZERO {
+            return Err(eyre!("Missing uninitialized account class hash"));
+        }
+        if self.account_contract_class_hash == FieldElement::ZERO {
+            return Err(eyre!("Missing account contract class hash"));
+        }
+        if self.kakarot_class_hash == Some(FieldElement::ZERO) {
+            return Err(eyre!("Invalid zero Kakarot class hash"));
+        }
+        Ok(())
+    }
+
+    /// Checks that the chain id of the Starknet provider matches the expected chain id.
+    /// The check is skipped if no expected chain id is configured.
+    pub async fn check_starknet_chain_id<P: Provider + Sync>(&self, provider: &P) -> Result<(), eyre::Error> {
+        let Some(expected) = self.expected_starknet_chain_id else {
+            return Ok(());
+        };
+        let chain_id = provider.chain_id().await?;
+        if chain_id != expected {
+            return Err(eyre!(
+                "Starknet provider chain id {chain_id:#x} does not match expected chain id {expected:#x}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parses a chain id given either as a hex string or as a Cairo short string (e.g. `SN_SEPOLIA`).
+fn parse_chain_id(chain_id: &str) -> Result<FieldElement, eyre::Error> {
+    if chain_id.starts_with("0x") {
+        Ok(FieldElement::from_hex_be(chain_id)?)
+    } else {
+        Ok(cairo_short_string_to_felt(chain_id)?)
+    }
 }
 
 /// A builder for a `JsonRpcClient`.
@@ -162,3 +247,44 @@ impl SequencerGatewayProviderBuilder {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(kakarot_address: FieldElement) -> KakarotRpcConfig {
+        KakarotRpcConfig::new(Network::Katana, kakarot_address, FieldElement::ONE, FieldElement::TWO)
+    }
+
+    #[test]
+    fn test_validate() {
+        // Given
+        let max_address = *STARKNET_ADDRESS_BOUND - FieldElement::ONE;
+
+        // When
+        let valid = config(FieldElement::from(0x1234_u64)).validate();
+        let max = config(max_address).validate();
+
+        // Then
+        assert!(valid.is_ok());
+        assert!(max.is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_configs() {
+        // Given
+        let zero_address = config(FieldElement::ZERO);
+        let out_of_bound_address = config(*STARKNET_ADDRESS_BOUND);
+        let missing_class_hash =
+            KakarotRpcConfig { account_contract_class_hash: FieldElement::ZERO, ..config(FieldElement::ONE) };
+        let zero_kakarot_class_hash = config(FieldElement::ONE).with_kakarot_class_hash(FieldElement::ZERO);
+
+        // When
+        let configs = [zero_address, out_of_bound_address, missing_class_hash, zero_kakarot_class_hash];
+
+        // Then
+        for config in configs {
+            assert!(config.validate().is_err(), "{config:?} should be invalid");
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use reth_primitives::B256;
+use reth_rpc_types::RichBlock;
+
+/// In-memory least recently used cache of the converted blocks, keyed by block hash and by
+/// whether the block holds the full transactions or only their hashes. The cache holds at
+/// most `capacity` blocks. Only sealed blocks, which can't change, should be inserted.
+#[derive(Debug)]
+pub struct BlockCache {
+    capacity: usize,
+    blocks: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Incremented on each access, used to find the least recently used block
+    tick: u64,
+    blocks: HashMap<(B256, bool), (u64, RichBlock)>,
+}
+
+impl BlockCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, blocks: Mutex::default() }
+    }
+
+    /// Returns the cached block with the given hash, marking it as the most recently used.
+    pub fn get(&self, hash: B256, full: bool) -> Option<RichBlock> {
+        let mut state = self.blocks.lock().ok()?;
+        state.tick += 1;
+        let tick = state.tick;
+        let (last_used, block) = state.blocks.get_mut(&(hash, full))?;
+        *last_used = tick;
+        Some(block.clone())
+    }
+
+    /// Caches the block with the given hash, evicting the least recently used block once the
+    /// cache holds more than `capacity` blocks.
+    pub fn insert(&self, hash: B256, full: bool, block: RichBlock) {
+        let Ok(mut state) = self.blocks.lock() else { return };
+        state.tick += 1;
+        let tick = state.tick;
+        state.blocks.insert((hash, full), (tick, block));
+        while state.blocks.len() > self.capacity {
+            let Some(oldest) = state.blocks.iter().min_by_key(|(_, (last_used, _))| *last_used).map(|(key, _)| *key)
+            else {
+                return;
+            };
+            state.blocks.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::{Block, Header};
+
+    fn block(number: u64) -> RichBlock {
+        Block { header: Header { number: Some(number), ..Default::default() }, ..Default::default() }.into()
+    }
+
+    #[test]
+    fn test_block_cache_full_and_hashes() {
+        // Given
+        let cache = BlockCache::new(2);
+        let hash = B256::repeat_byte(1);
+
+        // When
+        cache.insert(hash, true, block(1));
+
+        // Then
+        assert_eq!(cache.get(hash, true), Some(block(1)));
+        assert_eq!(cache.get(hash, false), None);
+    }
+
+    #[test]
+    fn test_block_cache_evicts_least_recently_used() {
+        // Given
+        let cache = BlockCache::new(2);
+        let (first, second, third) = (B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+        cache.insert(first, true, block(1));
+        cache.insert(second, true, block(2));
+
+        // When
+        let _ = cache.get(first, true);
+        cache.insert(third, true, block(3));
+
+        // Then
+        assert_eq!(cache.get(first, true), Some(block(1)));
+        assert_eq!(cache.get(second, true), None);
+        assert_eq!(cache.get(third, true), Some(block(3)));
+    }
+}
@@ -0,0 +1,115 @@
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use reth_primitives::{BlockNumberOrTag, U64};
+use reth_rpc_types::RichBlock;
+
+use super::error::EthApiError;
+use super::provider::{EthProviderResult, EthereumProvider};
+
+/// Streams the full blocks in order from the block `from`, fetching at most `prefetch` blocks
+/// ahead of the consumer. The stream ends after the latest block unless `follow_head` is set,
+/// in which case the latest block number is polled at the given interval for new blocks, the
+/// polls being retried with backoff while the provider is unreachable.
+/// A block which can't be fetched is yielded as an error, without ending the stream.
+pub fn block_stream<P>(
+    provider: P,
+    from: u64,
+    prefetch: usize,
+    follow_head: Option<Duration>,
+) -> impl Stream<Item = EthProviderResult<RichBlock>>
+where
+    P: EthereumProvider + Clone + Send + Sync,
+{
+    let numbers = stream::unfold(Some((from, None)), {
+        let provider = provider.clone();
+        move |state: Option<(u64, Option<u64>)>| {
+            let provider = provider.clone();
+            async move {
+                let (next, mut latest) = state?;
+                loop {
+                    if latest.is_some_and(|latest| next <= latest) {
+                        return Some((Ok(next), Some((next + 1, latest))));
+                    }
+                    // Only poll the head once the known blocks have been streamed
+                    if latest.is_some() {
+                        let interval = follow_head?;
+                        tokio::time::sleep(interval).await;
+                    }
+                    match follow_head {
+                        Some(interval) => {
+                            latest = Some(poll_head_with_backoff(|| provider.block_number(), interval).await);
+                        }
+                        None => match provider.block_number().await {
+                            Ok(number) => latest = Some(number.to()),
+                            // The stream ends after the error
+                            Err(err) => return Some((Err(err), None)),
+                        },
+                    }
+                }
+            }
+        }
+    });
+
+    numbers
+        .map(move |number| {
+            let provider = provider.clone();
+            async move {
+                provider
+                    .block_by_number(BlockNumberOrTag::Number(number?), true)
+                    .await?
+                    .ok_or(EthApiError::UnknownBlock)
+            }
+        })
+        .buffered(prefetch.max(1))
+}
+
+/// Maximum delay between two polls of the head after failures, as a multiple of the interval.
+const MAX_BACKOFF_FACTOR: u32 = 32;
+
+/// Polls the latest block number until it succeeds, waiting twice as long after each failure,
+/// up to `MAX_BACKOFF_FACTOR` times the interval.
+async fn poll_head_with_backoff<F, Fut>(mut poll: F, interval: Duration) -> u64
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = EthProviderResult<U64>>,
+{
+    let mut delay = interval;
+    loop {
+        match poll().await {
+            Ok(number) => return number.to(),
+            Err(err) => {
+                tracing::warn!("failed to poll the latest block, retrying in {delay:?}: {err}");
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2).min(interval.saturating_mul(MAX_BACKOFF_FACTOR));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[tokio::test]
+    async fn test_poll_head_with_backoff_resumes_after_disconnect() {
+        // Given
+        // The provider is unreachable for the first two polls
+        let polls = AtomicU64::new(0);
+        let poll = || async {
+            match polls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(EthApiError::UnknownBlockNumber),
+                _ => Ok(U64::from(5)),
+            }
+        };
+
+        // When
+        let head = poll_head_with_backoff(poll, Duration::from_millis(1)).await;
+
+        // Then
+        assert_eq!(head, 5);
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,388 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, U256, U64};
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use reth_rpc_types::txpool::TxpoolContent;
+use reth_rpc_types::{
+    FeeHistory, Filter, FilterChanges, Header, Index, RichBlock, SyncStatus, Transaction, TransactionReceipt,
+    TransactionRequest,
+};
+use starknet::core::types::MaybePendingBlockWithTxs;
+use starknet::providers::ProviderError;
+
+use super::error::{EthApiError, KakarotError};
+use super::provider::{EthProviderResult, EthereumProvider};
+use crate::models::log::{LogCursor, PaginatedLogs};
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests go through, counting the consecutive failures.
+    Closed { failures: u32 },
+    /// Requests are short-circuited until the end of the cooldown.
+    Open { until: Instant },
+    /// A single request is going through to test the recovery of the provider. Another
+    /// request is let through if the probe didn't complete within the cooldown.
+    HalfOpen { since: Instant },
+}
+
+/// Circuit breaker short-circuiting the requests after `failure_threshold` consecutive
+/// failures of the Starknet provider, for a `cooldown` period. Once the cooldown is over,
+/// a single request is let through: the circuit closes if it succeeds and opens again
+/// otherwise. A threshold of zero disables the circuit breaker.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+    pub const fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self { failure_threshold, cooldown, state: Mutex::new(CircuitState::Closed { failures: 0 }) }
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        *self.state.lock().expect("circuit breaker lock poisoned")
+    }
+
+    /// Runs the request if the circuit allows it, and records its outcome.
+    pub async fn call<T>(&self, request: impl Future<Output = EthProviderResult<T>> + Send) -> EthProviderResult<T> {
+        if self.failure_threshold == 0 {
+            return request.await;
+        }
+        let probe = self.acquire()?;
+        let result = request.await;
+        self.record(result.as_ref().err().is_some_and(is_provider_failure), probe);
+        result
+    }
+
+    /// Returns an error if the request should be short-circuited. Otherwise returns the start
+    /// of the probe if the request is the one testing the recovery of the provider.
+    fn acquire(&self) -> EthProviderResult<Option<Instant>> {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let now = Instant::now();
+        match *state {
+            CircuitState::Closed { .. } => Ok(None),
+            CircuitState::Open { until } if now >= until => {
+                *state = CircuitState::HalfOpen { since: now };
+                Ok(Some(now))
+            }
+            CircuitState::HalfOpen { since } if now >= since + self.cooldown => {
+                *state = CircuitState::HalfOpen { since: now };
+                Ok(Some(now))
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen { .. } => Err(EthApiError::ProviderUnavailable),
+        }
+    }
+
+    /// Records the outcome of a request which went through. Once the circuit opened, only the
+    /// outcome of the current probe changes its state: the requests started before don't.
+    fn record(&self, failed: bool, probe: Option<Instant>) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let open = CircuitState::Open { until: Instant::now() + self.cooldown };
+        *state = match (*state, failed) {
+            (CircuitState::Closed { .. }, false) => CircuitState::Closed { failures: 0 },
+            (CircuitState::Closed { failures }, true) if failures + 1 < self.failure_threshold => {
+                CircuitState::Closed { failures: failures + 1 }
+            }
+            (CircuitState::Closed { .. }, true) => open,
+            (CircuitState::HalfOpen { since }, false) if probe == Some(since) => CircuitState::Closed { failures: 0 },
+            (CircuitState::HalfOpen { since }, true) if probe == Some(since) => open,
+            (state @ (CircuitState::Open { .. } | CircuitState::HalfOpen { .. }), _) => state,
+        };
+    }
+}
+
+/// Returns true if the error means the Starknet provider could not be reached. Errors
+/// returned by the Starknet node itself show the provider is up.
+fn is_provider_failure(error: &EthApiError) -> bool {
+    let error = match error {
+        EthApiError::Kakarot(KakarotError::ProviderError(error)) => error,
+        EthApiError::Kakarot(KakarotError::CallError(cainome::cairo_serde::Error::Provider(error))) => error,
+        _ => return false,
+    };
+    matches!(error, ProviderError::RateLimited | ProviderError::Other(_))
+}
+
+/// Ethereum provider guarding the requests of an inner provider with a circuit breaker.
+#[derive(Debug)]
+pub struct CircuitBreakerProvider<P> {
+    inner: P,
+    circuit_breaker: CircuitBreaker,
+}
+
+impl<P> CircuitBreakerProvider<P> {
+    pub const fn new(inner: P, circuit_breaker: CircuitBreaker) -> Self {
+        Self { inner, circuit_breaker }
+    }
+
+    /// Returns the circuit breaker guarding the requests.
+    pub const fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+}
+
+#[async_trait]
+impl<P> EthereumProvider for CircuitBreakerProvider<P>
+where
+    P: EthereumProvider + Send + Sync,
+{
+    async fn header(&self, block_id: &BlockId) -> EthProviderResult<Option<Header>> {
+        self.circuit_breaker.call(self.inner.header(block_id)).await
+    }
+
+    async fn block_number(&self) -> EthProviderResult<U64> {
+        self.circuit_breaker.call(self.inner.block_number()).await
+    }
+
+    async fn syncing(&self) -> EthProviderResult<SyncStatus> {
+        self.circuit_breaker.call(self.inner.syncing()).await
+    }
+
+    async fn chain_id(&self) -> EthProviderResult<Option<U64>> {
+        self.circuit_breaker.call(self.inner.chain_id()).await
+    }
+
+    async fn block_by_hash(&self, hash: B256, full: bool) -> EthProviderResult<Option<RichBlock>> {
+        self.circuit_breaker.call(self.inner.block_by_hash(hash, full)).await
+    }
+
+    async fn block_by_number(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+        full: bool,
+    ) -> EthProviderResult<Option<RichBlock>> {
+        self.circuit_breaker.call(self.inner.block_by_number(number_or_tag, full)).await
+    }
+
+    async fn block_transaction_count_by_hash(&self, hash: B256) -> EthProviderResult<Option<U256>> {
+        self.circuit_breaker.call(self.inner.block_transaction_count_by_hash(hash)).await
+    }
+
+    async fn block_transaction_count_by_number(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+    ) -> EthProviderResult<Option<U256>> {
+        self.circuit_breaker.call(self.inner.block_transaction_count_by_number(number_or_tag)).await
+    }
+
+    async fn transaction_by_hash(&self, hash: B256) -> EthProviderResult<Option<reth_rpc_types::Transaction>> {
+        self.circuit_breaker.call(self.inner.transaction_by_hash(hash)).await
+    }
+
+    async fn transaction_by_block_hash_and_index(
+        &self,
+        hash: B256,
+        index: Index,
+    ) -> EthProviderResult<Option<reth_rpc_types::Transaction>> {
+        self.circuit_breaker.call(self.inner.transaction_by_block_hash_and_index(hash, index)).await
+    }
+
+    async fn transaction_by_block_number_and_index(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+        index: Index,
+    ) -> EthProviderResult<Option<reth_rpc_types::Transaction>> {
+        self.circuit_breaker.call(self.inner.transaction_by_block_number_and_index(number_or_tag, index)).await
+    }
+
+    async fn transaction_receipt(&self, hash: B256) -> EthProviderResult<Option<TransactionReceipt>> {
+        self.circuit_breaker.call(self.inner.transaction_receipt(hash)).await
+    }
+
+    async fn balance(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<U256> {
+        self.circuit_breaker.call(self.inner.balance(address, block_id)).await
+    }
+
+    async fn storage_at(
+        &self,
+        address: Address,
+        index: JsonStorageKey,
+        block_id: Option<BlockId>,
+    ) -> EthProviderResult<B256> {
+        self.circuit_breaker.call(self.inner.storage_at(address, index, block_id)).await
+    }
+
+    async fn transaction_count(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<U256> {
+        self.circuit_breaker.call(self.inner.transaction_count(address, block_id)).await
+    }
+
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<Bytes> {
+        self.circuit_breaker.call(self.inner.get_code(address, block_id)).await
+    }
+
+    async fn code_hash(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<B256> {
+        self.circuit_breaker.call(self.inner.code_hash(address, block_id)).await
+    }
+
+    async fn get_logs(&self, filter: Filter) -> EthProviderResult<FilterChanges> {
+        self.circuit_breaker.call(self.inner.get_logs(filter)).await
+    }
+
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        page_size: u64,
+    ) -> EthProviderResult<PaginatedLogs> {
+        self.circuit_breaker.call(self.inner.get_logs_paginated(filter, cursor, page_size)).await
+    }
+
+    async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>) -> EthProviderResult<Bytes> {
+        self.circuit_breaker.call(self.inner.call(request, block_id)).await
+    }
+
+    async fn estimate_gas(&self, call: TransactionRequest, block_id: Option<BlockId>) -> EthProviderResult<U256> {
+        self.circuit_breaker.call(self.inner.estimate_gas(call, block_id)).await
+    }
+
+    async fn fee_history(
+        &self,
+        block_count: U64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> EthProviderResult<FeeHistory> {
+        self.circuit_breaker.call(self.inner.fee_history(block_count, newest_block, reward_percentiles)).await
+    }
+
+    async fn send_raw_transaction(&self, transaction: Bytes) -> EthProviderResult<B256> {
+        self.circuit_breaker.call(self.inner.send_raw_transaction(transaction)).await
+    }
+
+    async fn gas_price(&self) -> EthProviderResult<U256> {
+        self.circuit_breaker.call(self.inner.gas_price()).await
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> EthProviderResult<U256> {
+        self.circuit_breaker.call(self.inner.max_priority_fee_per_gas()).await
+    }
+
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> EthProviderResult<Option<Vec<TransactionReceipt>>> {
+        self.circuit_breaker.call(self.inner.block_receipts(block_id)).await
+    }
+
+    async fn block_transactions(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> EthProviderResult<Option<Vec<reth_rpc_types::Transaction>>> {
+        self.circuit_breaker.call(self.inner.block_transactions(block_id)).await
+    }
+
+    async fn txpool_transactions(&self) -> EthProviderResult<Vec<Transaction>> {
+        self.circuit_breaker.call(self.inner.txpool_transactions()).await
+    }
+
+    async fn txpool_content(&self) -> EthProviderResult<TxpoolContent> {
+        self.circuit_breaker.call(self.inner.txpool_content()).await
+    }
+
+    async fn starknet_block(&self, block_id: Option<BlockId>) -> EthProviderResult<MaybePendingBlockWithTxs> {
+        self.circuit_breaker.call(self.inner.starknet_block(block_id)).await
+    }
+
+    async fn starknet_transaction_hash(&self, hash: B256) -> EthProviderResult<Option<B256>> {
+        self.circuit_breaker.call(self.inner.starknet_transaction_hash(hash)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn failing_request() -> EthProviderResult<()> {
+        Err(KakarotError::from(ProviderError::RateLimited).into())
+    }
+
+    async fn succeeding_request() -> EthProviderResult<()> {
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens() {
+        // Given
+        let circuit_breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        // When
+        let _ = circuit_breaker.call(failing_request()).await;
+        let closed = circuit_breaker.state();
+        let _ = circuit_breaker.call(failing_request()).await;
+
+        // Then
+        assert_eq!(closed, CircuitState::Closed { failures: 1 });
+        assert!(matches!(circuit_breaker.state(), CircuitState::Open { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_cooldown() {
+        // Given
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let _ = circuit_breaker.call(failing_request()).await;
+
+        // When
+        let result = circuit_breaker.call(succeeding_request()).await;
+
+        // Then
+        assert!(matches!(result, Err(EthApiError::ProviderUnavailable)));
+        assert!(matches!(circuit_breaker.state(), CircuitState::Open { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_half_open() {
+        // Given
+        let circuit_breaker = CircuitBreaker::new(1, Duration::ZERO);
+        let _ = circuit_breaker.call(failing_request()).await;
+
+        // When
+        // The probe fails and the circuit opens again
+        let _ = circuit_breaker.call(failing_request()).await;
+        let reopened = circuit_breaker.state();
+        // The probe succeeds and the circuit closes
+        let result = circuit_breaker.call(succeeding_request()).await;
+
+        // Then
+        assert!(matches!(reopened, CircuitState::Open { .. }));
+        assert!(result.is_ok());
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed { failures: 0 });
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_ignores_requests_started_before_opening() {
+        // Given
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let slow_request = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(())
+        };
+
+        // When
+        // The slow request goes through while the circuit is closed, and completes once it opened
+        let (result, _) = tokio::join!(circuit_breaker.call(slow_request), circuit_breaker.call(failing_request()));
+
+        // Then
+        assert!(result.is_ok());
+        assert!(matches!(circuit_breaker.state(), CircuitState::Open { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_ignores_starknet_errors() {
+        // Given
+        let circuit_breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        let starknet_error = || async {
+            Err::<(), _>(
+                KakarotError::from(ProviderError::StarknetError(starknet::core::types::StarknetError::BlockNotFound))
+                    .into(),
+            )
+        };
+
+        // When
+        let _ = circuit_breaker.call(starknet_error()).await;
+
+        // Then
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed { failures: 0 });
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use eyre::{eyre, Result};
+use reth_primitives::{Address, B256};
+
+use super::constant::KAKAROT_SYSTEM_EVENTS;
+use super::utils::keccak::event_topic;
+use crate::models::block::PendingBlockHash;
+
+/// Configuration of the Ethereum provider, read from the environment by [`KakarotConfig::from_env`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KakarotConfig {
+    /// Block number the earliest tag resolves to.
+    pub earliest_block_number: u64,
+    /// Address reported as the miner of blocks, the sequencer address truncated to an Ethereum
+    /// address if not set.
+    pub coinbase: Option<Address>,
+    /// Gas limit reported for all blocks, the gas limit stored with each block if not set.
+    pub block_gas_limit: Option<u128>,
+    /// Gas limit of a call without gas limit, the gas limit of the block if not set.
+    pub call_gas_limit: Option<u128>,
+    /// Hash returned for the pending block.
+    pub pending_block_hash: PendingBlockHash,
+    /// First topics of the logs excluded from the logs queries, the topics of the internal events
+    /// of Kakarot by default.
+    pub denied_log_topics: Vec<B256>,
+    /// Maximum number of logs returned by a single logs query, unlimited if not set.
+    pub max_logs: Option<u64>,
+    /// Whether the logs queries skip the blocks whose header bloom excludes the filter.
+    pub logs_bloom_filter: bool,
+    /// Number of Starknet fee units a wei of EVM fee is worth.
+    pub starknet_fee_ratio: u64,
+    /// Number of decimals of the fee token, used to convert its balances into wei.
+    pub fee_token_decimals: u8,
+    /// Floor of the gas price and of the max priority fee per gas.
+    pub min_gas_price: u128,
+    /// Number of most recent Starknet blocks scanned for a transaction missing from the
+    /// database, the scan is disabled if zero.
+    pub transaction_scan_depth: u64,
+    /// Whether the L1 handler transactions sent to Kakarot, i.e. the deposits, are returned
+    /// by the scan of the most recent Starknet blocks.
+    pub include_l1_handlers: bool,
+    /// Only senders allowed to send transactions, all senders are allowed if not set.
+    pub sender_allowlist: Option<Vec<Address>>,
+    /// Whether the reverted transactions are left out of the transactions of blocks.
+    pub exclude_reverted_transactions: bool,
+    /// Whether the Starknet transactions whose recovered signer doesn't match the sender are
+    /// rejected instead of converted with a warning.
+    pub strict_signature_checks: bool,
+    /// Number of converted sealed blocks held in memory, the block cache is disabled if zero.
+    pub block_cache_size: usize,
+    /// Number of most recent blocks whose logs are held in memory, the index is disabled if zero.
+    #[cfg(feature = "log-index")]
+    pub log_index_blocks: u64,
+    /// Number of consecutive Starknet provider failures opening the circuit breaker, the circuit
+    /// breaker is disabled if zero.
+    pub circuit_breaker_threshold: u32,
+    /// Time requests are short-circuited for once the circuit breaker opens.
+    pub circuit_breaker_cooldown: Duration,
+}
+
+impl Default for KakarotConfig {
+    fn default() -> Self {
+        Self {
+            earliest_block_number: 0,
+            coinbase: None,
+            block_gas_limit: None,
+            call_gas_limit: None,
+            pending_block_hash: PendingBlockHash::default(),
+            denied_log_topics: default_denied_log_topics(),
+            max_logs: None,
+            logs_bloom_filter: false,
+            starknet_fee_ratio: 1,
+            fee_token_decimals: 18,
+            min_gas_price: 0,
+            transaction_scan_depth: 0,
+            include_l1_handlers: false,
+            sender_allowlist: None,
+            exclude_reverted_transactions: false,
+            strict_signature_checks: false,
+            block_cache_size: 0,
+            #[cfg(feature = "log-index")]
+            log_index_blocks: 128,
+            circuit_breaker_threshold: 0,
+            circuit_breaker_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl KakarotConfig {
+    /// Reads the configuration from the environment, unset or empty variables keeping their
+    /// default value, and validates it.
+    pub fn from_env() -> Result<Self> {
+        let default = Self::default();
+        let pending_block_hash = std::env::var("PENDING_BLOCK_HASH")
+            .ok()
+            .filter(|mode| !mode.is_empty())
+            .map(|mode| match mode.to_lowercase().as_str() {
+                "placeholder" => Ok(PendingBlockHash::Placeholder),
+                "null" => Ok(PendingBlockHash::Null),
+                _ => Err(eyre!("Invalid PENDING_BLOCK_HASH: {mode}")),
+            })
+            .transpose()?
+            .unwrap_or(default.pending_block_hash);
+
+        let config = Self {
+            earliest_block_number: parse_from_env("EARLIEST_BLOCK_NUMBER")?.unwrap_or(default.earliest_block_number),
+            coinbase: parse_from_env("COINBASE_ADDRESS")?,
+            block_gas_limit: parse_from_env("BLOCK_GAS_LIMIT")?,
+            call_gas_limit: parse_from_env("CALL_GAS_LIMIT")?,
+            pending_block_hash,
+            denied_log_topics: list_from_env("DENIED_LOG_TOPICS", parse_denied_log_topic)?
+                .map_or(default.denied_log_topics, |topics| topics.concat()),
+            max_logs: parse_from_env("MAX_LOGS")?,
+            logs_bloom_filter: flag_from_env("LOGS_BLOOM_FILTER"),
+            starknet_fee_ratio: parse_from_env("STARKNET_FEE_RATIO")?.unwrap_or(default.starknet_fee_ratio),
+            fee_token_decimals: parse_from_env("FEE_TOKEN_DECIMALS")?.unwrap_or(default.fee_token_decimals),
+            min_gas_price: parse_from_env("MIN_GAS_PRICE")?.unwrap_or(default.min_gas_price),
+            transaction_scan_depth: parse_from_env("TRANSACTION_SCAN_DEPTH")?.unwrap_or(default.transaction_scan_depth),
+            include_l1_handlers: flag_from_env("INCLUDE_L1_HANDLERS"),
+            sender_allowlist: list_from_env("SENDER_ALLOWLIST", Address::from_str)?,
+            exclude_reverted_transactions: flag_from_env("EXCLUDE_REVERTED_TRANSACTIONS"),
+            strict_signature_checks: flag_from_env("STRICT_SIGNATURE_CHECKS"),
+            block_cache_size: parse_from_env("BLOCK_CACHE_SIZE")?.unwrap_or(default.block_cache_size),
+            #[cfg(feature = "log-index")]
+            log_index_blocks: parse_from_env("LOG_INDEX_BLOCKS")?.unwrap_or(default.log_index_blocks),
+            circuit_breaker_threshold: parse_from_env("CIRCUIT_BREAKER_THRESHOLD")?
+                .unwrap_or(default.circuit_breaker_threshold),
+            circuit_breaker_cooldown: parse_from_env("CIRCUIT_BREAKER_COOLDOWN")?
+                .map_or(default.circuit_breaker_cooldown, Duration::from_secs),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the configuration is usable: the gas limits and the Starknet fee ratio must
+    /// be nonzero.
+    pub fn validate(&self) -> Result<()> {
+        if self.block_gas_limit == Some(0) {
+            return Err(eyre!("BLOCK_GAS_LIMIT must be nonzero"));
+        }
+        if self.call_gas_limit == Some(0) {
+            return Err(eyre!("CALL_GAS_LIMIT must be nonzero"));
+        }
+        if self.starknet_fee_ratio == 0 {
+            return Err(eyre!("STARKNET_FEE_RATIO must be nonzero"));
+        }
+        Ok(())
+    }
+}
+
+/// Parses the value of an environment variable, returning `None` if it is unset or empty.
+fn parse_from_env<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| value.trim().parse().map_err(|err| eyre!("Invalid {name}: {err}")))
+        .transpose()
+}
+
+/// Parses a comma separated list from the environment, returning `None` if it is unset or empty.
+/// The commas between parentheses don't separate items, e.g. in the signature of an event.
+fn list_from_env<T, E: Display>(name: &str, parse: impl Fn(&str) -> Result<T, E>) -> Result<Option<Vec<T>>> {
+    let Some(list) = std::env::var(name).ok().filter(|list| !list.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let mut depth = 0_usize;
+    list.split(|c| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        c == ',' && depth == 0
+    })
+    .map(str::trim)
+    .filter(|item| !item.is_empty())
+    .map(|item| parse(item).map_err(|err| eyre!("Invalid {name}: {err}")))
+    .collect::<Result<_>>()
+    .map(Some)
+}
+
+/// Returns the topics of the internal events of Kakarot, denied by default.
+fn default_denied_log_topics() -> Vec<B256> {
+    KAKAROT_SYSTEM_EVENTS.into_iter().map(event_topic).collect()
+}
+
+/// Parses a log topic, given either as a hash or as the signature of an event, e.g.
+/// `Transfer(address,address,uint256)`.
+fn parse_log_topic(topic: &str) -> Result<B256, <B256 as FromStr>::Err> {
+    if topic.contains('(') {
+        Ok(event_topic(topic))
+    } else {
+        B256::from_str(topic)
+    }
+}
+
+/// Parses an item of the denied log topics, where `default` stands for the default topics, so
+/// that the list extends the default topics if it contains it and overrides them otherwise.
+fn parse_denied_log_topic(topic: &str) -> Result<Vec<B256>, <B256 as FromStr>::Err> {
+    if topic.eq_ignore_ascii_case("default") {
+        Ok(default_denied_log_topics())
+    } else {
+        parse_log_topic(topic).map(|topic| vec![topic])
+    }
+}
+
+/// Reads a boolean flag from the environment, which is only set by `true`.
+fn flag_from_env(name: &str) -> bool {
+    std::env::var(name).map(|flag| flag.eq_ignore_ascii_case("true")).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        // Given
+        let default = KakarotConfig::default();
+        let configured = KakarotConfig {
+            block_gas_limit: Some(30_000_000),
+            call_gas_limit: Some(5_000_000),
+            starknet_fee_ratio: 10,
+            ..Default::default()
+        };
+
+        // When
+        let results = [default.validate(), configured.validate()];
+
+        // Then
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_configs() {
+        // Given
+        let configs = [
+            KakarotConfig { block_gas_limit: Some(0), ..Default::default() },
+            KakarotConfig { call_gas_limit: Some(0), ..Default::default() },
+            KakarotConfig { starknet_fee_ratio: 0, ..Default::default() },
+        ];
+
+        // When
+        let results = configs.map(|config| config.validate());
+
+        // Then
+        assert!(results.iter().all(Result::is_err));
+    }
+
+    #[test]
+    fn test_parse_from_env() {
+        // Given
+        std::env::set_var("TEST_KAKAROT_CONFIG_ADDRESS", "0x5FbDB2315678afecb367f032d93F642f64180aa3");
+        std::env::set_var("TEST_KAKAROT_CONFIG_INVALID_ADDRESS", "0x1234");
+        std::env::set_var("TEST_KAKAROT_CONFIG_EMPTY", " ");
+
+        // When
+        let address = parse_from_env::<Address>("TEST_KAKAROT_CONFIG_ADDRESS");
+        let invalid_address = parse_from_env::<Address>("TEST_KAKAROT_CONFIG_INVALID_ADDRESS");
+        let empty = parse_from_env::<u128>("TEST_KAKAROT_CONFIG_EMPTY");
+        let unset = list_from_env("TEST_KAKAROT_CONFIG_UNSET", B256::from_str);
+
+        // Then
+        assert!(address.unwrap().is_some());
+        assert!(invalid_address.is_err());
+        assert!(empty.unwrap().is_none());
+        assert!(unset.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_denied_log_topics_from_env() {
+        // Given
+        let approval = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+        std::env::set_var("TEST_KAKAROT_CONFIG_TOPICS", format!("Transfer(address,address,uint256), {approval}"));
+
+        // When
+        let topics = list_from_env("TEST_KAKAROT_CONFIG_TOPICS", parse_log_topic).unwrap().unwrap();
+
+        // Then
+        let transfer = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+        assert_eq!(topics, vec![B256::from_str(transfer).unwrap(), B256::from_str(approval).unwrap()]);
+    }
+
+    #[test]
+    fn test_denied_log_topics_extend_or_override_default() {
+        // Given
+        std::env::set_var("TEST_KAKAROT_CONFIG_EXTENDED_TOPICS", "default, Transfer(address,address,uint256)");
+        std::env::set_var("TEST_KAKAROT_CONFIG_OVERRIDDEN_TOPICS", "Transfer(address,address,uint256)");
+
+        // When
+        let extended = list_from_env("TEST_KAKAROT_CONFIG_EXTENDED_TOPICS", parse_denied_log_topic).unwrap().unwrap();
+        let overridden =
+            list_from_env("TEST_KAKAROT_CONFIG_OVERRIDDEN_TOPICS", parse_denied_log_topic).unwrap().unwrap();
+
+        // Then
+        let transfer = event_topic("Transfer(address,address,uint256)");
+        assert_eq!(KakarotConfig::default().denied_log_topics.len(), KAKAROT_SYSTEM_EVENTS.len());
+        assert_eq!(extended.concat(), [KakarotConfig::default().denied_log_topics, vec![transfer]].concat());
+        assert_eq!(overridden.concat(), vec![transfer]);
+    }
+}
@@ -3,9 +3,47 @@ use reth_primitives::U256;
 
 lazy_static! {
     pub static ref MAX_PRIORITY_FEE_PER_GAS: u64 = 0;
+
+    // Expose the debug methods of the kakarot namespace, e.g. kakarot_getStarknetBlock
+    pub static ref KAKAROT_DEBUG_ENDPOINTS: bool = std::env::var("KAKAROT_DEBUG_ENDPOINTS")
+        .map(|enabled| enabled.eq_ignore_ascii_case("true"))
+        .unwrap_or_default();
+
+    // Maximum number of values read by a single batched call of the kakarot namespace,
+    // e.g. the slots of kakarot_getStorageAtBatch or the addresses of kakarot_getBalances
+    pub static ref KAKAROT_MAX_BATCH_VALUES: usize = std::env::var("KAKAROT_MAX_BATCH_VALUES")
+        .ok()
+        .and_then(|max| max.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BATCH_VALUES);
+
+    // Fetch the latest block number and base fee at startup, before accepting requests
+    pub static ref WARM_UP: bool = std::env::var("WARM_UP")
+        .map(|enabled| enabled.eq_ignore_ascii_case("true"))
+        .unwrap_or_default();
+
+    // Add the non-standard debug fields to the responses, e.g. the Starknet hash of receipts
+    pub static ref KAKAROT_DEBUG_FIELDS: bool = std::env::var("KAKAROT_DEBUG_FIELDS")
+        .map(|enabled| enabled.eq_ignore_ascii_case("true"))
+        .unwrap_or_default();
 }
 
-/// Gas limit for estimate gas and call
+/// Maximum number of blocks of a logs query whose headers are read to skip the blocks on their bloom
+pub const LOGS_BLOOM_FILTER_MAX_BLOCKS: u64 = 10_000;
+/// Number of logs returned by a paginated logs query when no page size is given
+pub const DEFAULT_LOGS_PAGE_SIZE: u64 = 1000;
+/// Maximum number of transaction hashes remembered as missing from the scanned Starknet blocks
+pub const TRANSACTION_SCAN_MISSES: usize = 1024;
+/// Maximum number of values read by a batched call when `KAKAROT_MAX_BATCH_VALUES` isn't set
+pub const DEFAULT_MAX_BATCH_VALUES: usize = 1024;
+/// Maximum number of values read concurrently by a batched call, e.g. `kakarot_getStorageAtBatch`
+pub const BATCH_CONCURRENCY: usize = 16;
+/// Signatures of the internal events of Kakarot, the fee transfers and the sequencer payments,
+/// whose logs are excluded from the logs queries by default
+pub const KAKAROT_SYSTEM_EVENTS: [&str; 2] =
+    ["FeeTransfer(address,address,uint256)", "SequencerPayment(address,uint256)"];
+/// Maximum number of seconds a block timestamp can be ahead of the current time before being reported
+pub const MAX_TIMESTAMP_DRIFT: u64 = 24 * 60 * 60;
+/// Gas limit for a call without gas limit, when the gas limit of the block is unknown
 pub const CALL_REQUEST_GAS_LIMIT: u128 = 5_000_000;
 /// Number of characters for representing a U256 in a hex string form. Used for padding hashes
 pub const HASH_HEX_STRING_LEN: usize = 64;
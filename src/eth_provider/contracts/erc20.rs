@@ -1,3 +1,4 @@
+use alloy_sol_types::sol_data;
 use ethers::abi::AbiEncode;
 use ethers::core::types::Address as EthersAddress;
 use ethers::prelude::abigen;
@@ -7,9 +8,9 @@ use reth_primitives::{BlockId, TxKind, U256};
 use reth_rpc_types::request::TransactionInput;
 use reth_rpc_types::TransactionRequest;
 
-use crate::eth_provider::error::KakarotError;
 use crate::eth_provider::provider::EthProviderResult;
 use crate::eth_provider::provider::EthereumProvider;
+use crate::eth_provider::utils::decode_call_result;
 
 // abigen generates a lot of unused code, needs to be benchmarked if performances ever become a
 // concern
@@ -49,9 +50,7 @@ impl<P: EthereumProvider> EthereumErc20<P> {
         };
 
         let ret = self.provider.call(request, Some(block_id)).await?;
-        let balance = U256::try_from_be_slice(&ret).ok_or(KakarotError::CallError(
-            cainome::cairo_serde::Error::Deserialize("failed to deserialize balance".to_string()),
-        ))?;
+        let (balance,) = decode_call_result::<(sol_data::Uint<256>,)>(&ret)?;
 
         Ok(balance)
     }
@@ -2,10 +2,10 @@ pub mod types;
 
 use super::error::KakarotError;
 use crate::eth_provider::database::types::{
-    header::StoredHeader,
+    header::{StoredHeader, StoredHeaderBloom},
     log::StoredLog,
     receipt::StoredTransactionReceipt,
-    transaction::{StoredPendingTransaction, StoredTransaction, StoredTransactionHash},
+    transaction::{StoredPendingTransaction, StoredStarknetTransactionHash, StoredTransaction, StoredTransactionHash},
 };
 use futures::TryStreamExt;
 use itertools::Itertools;
@@ -97,6 +97,16 @@ impl Database {
         Ok(cursor.try_next().await?.map(|doc| mongodb::bson::de::from_document(doc)).transpose()?)
     }
 
+    /// Get a list of documents from aggregated collections
+    pub async fn get_aggregate<T>(&self, pipeline: impl IntoIterator<Item = Document>) -> DatabaseResult<Vec<T>>
+    where
+        T: DeserializeOwned + CollectionName,
+    {
+        let documents: Vec<Document> = self.collection::<T>().aggregate(pipeline, None).await?.try_collect().await?;
+
+        Ok(documents.into_iter().map(mongodb::bson::de::from_document).collect::<Result<_, _>>()?)
+    }
+
     /// Update a single document in a collection
     pub async fn update_one<T>(&self, doc: T, filter: impl Into<Document>, upsert: bool) -> DatabaseResult<()>
     where
@@ -153,6 +163,12 @@ impl CollectionName for StoredHeader {
 }
 
 /// Implement [`CollectionName`] for [`StoredTransaction`]
+impl CollectionName for StoredHeaderBloom {
+    fn collection_name() -> &'static str {
+        "headers"
+    }
+}
+
 impl CollectionName for StoredTransaction {
     fn collection_name() -> &'static str {
         "transactions"
@@ -173,6 +189,13 @@ impl CollectionName for StoredTransactionHash {
     }
 }
 
+/// Implement [`CollectionName`] for [`StoredStarknetTransactionHash`]
+impl CollectionName for StoredStarknetTransactionHash {
+    fn collection_name() -> &'static str {
+        "transactions_starknet_hashes"
+    }
+}
+
 /// Implement [`CollectionName`] for [`StoredTransactionReceipt`]
 impl CollectionName for StoredTransactionReceipt {
     fn collection_name() -> &'static str {
@@ -1,19 +1,53 @@
+use std::collections::HashMap;
+
+use reth_primitives::{Address, Bloom, B256, U64};
 use reth_rpc_types::Header;
-use serde::{Deserialize, Serialize};
+use serde::{de::value::MapDeserializer, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 #[cfg(any(test, feature = "arbitrary", feature = "testing"))]
 use {
     arbitrary::Arbitrary,
-    reth_primitives::{constants::EMPTY_ROOT_HASH, B256, B64, U256},
+    reth_primitives::{constants::EMPTY_ROOT_HASH, B64, U256},
 };
 
 /// A header as stored in the database
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "arbitrary", feature = "testing"), derive(arbitrary::Arbitrary))]
 pub struct StoredHeader {
-    #[serde(deserialize_with = "crate::eth_provider::database::types::serde::deserialize_intermediate")]
+    #[serde(deserialize_with = "deserialize_header")]
     pub header: Header,
 }
 
+/// The fields of a stored header needed to skip the blocks of a logs query on their bloom
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct StoredHeaderBloom {
+    pub header: HeaderBloom,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderBloom {
+    pub number: U64,
+    pub hash: Option<B256>,
+    pub logs_bloom: Bloom,
+}
+
+/// Deserializes a stored header like [`deserialize_intermediate`](super::serde::deserialize_intermediate).
+/// Headers of old blocks, for which the Starknet provider didn't return a sequencer address,
+/// can be stored without a miner: it then defaults to the zero address.
+fn deserialize_header<'de, D>(deserializer: D) -> Result<Header, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut s: HashMap<String, Value> = HashMap::deserialize(deserializer)?;
+    let miner = s.entry("miner".to_string()).or_insert(Value::Null);
+    if miner.is_null() {
+        *miner = serde_json::json!(Address::ZERO);
+    }
+    let deserializer = MapDeserializer::new(s.into_iter());
+    Header::deserialize(deserializer).map_err(|err: serde_json::Error| serde::de::Error::custom(err.to_string()))
+}
+
 #[cfg(any(test, feature = "arbitrary", feature = "testing"))]
 impl<'a> StoredHeader {
     pub fn arbitrary_with_optional_fields(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
@@ -49,4 +83,21 @@ mod tests {
 
         let _ = StoredHeader::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
+
+    #[test]
+    fn test_stored_header_missing_miner() {
+        // Given
+        let mut bytes = [0u8; 1024];
+        rand::thread_rng().fill(bytes.as_mut_slice());
+        let header = StoredHeader::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
+        let mut value = serde_json::to_value(&header).unwrap();
+        value["header"].as_object_mut().unwrap().remove("miner");
+
+        // When
+        let stored: StoredHeader = serde_json::from_value(value).unwrap();
+
+        // Then
+        assert_eq!(stored.header.miner, Address::ZERO);
+        assert_eq!(stored.header.hash, header.header.hash);
+    }
 }
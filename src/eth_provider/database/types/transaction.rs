@@ -1,5 +1,5 @@
 use reth_primitives::B256;
-use reth_rpc_types::Transaction;
+use reth_rpc_types::{Parity, Transaction};
 use serde::{Deserialize, Serialize};
 #[cfg(any(test, feature = "arbitrary", feature = "testing"))]
 use {arbitrary::Arbitrary, reth_primitives::TxType};
@@ -14,7 +14,15 @@ pub struct StoredTransaction {
 
 impl From<StoredTransaction> for Transaction {
     fn from(tx: StoredTransaction) -> Self {
-        tx.tx
+        let mut tx = tx.tx;
+        // Typed transactions report the parity of their signature as `yParity`, which the
+        // transactions indexed without it only hold in `v`
+        if tx.transaction_type.is_some_and(|ty| ty != 0) {
+            if let Some(signature) = tx.signature.as_mut() {
+                signature.y_parity = signature.y_parity.or(Some(Parity(!signature.v.is_zero())));
+            }
+        }
+        tx
     }
 }
 
@@ -119,6 +127,16 @@ impl From<StoredTransactionHash> for B256 {
     }
 }
 
+/// The Starknet hash of a transaction sent through the RPC, as stored in the database
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredStarknetTransactionHash {
+    /// Ethereum hash of the transaction
+    pub eth_hash: B256,
+    /// Starknet hash of the invoke transaction which carries the transaction
+    pub starknet_hash: B256,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +150,33 @@ mod tests {
 
         let _ = StoredTransaction::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
+
+    #[test]
+    fn test_stored_transaction_y_parity() {
+        // Given
+        let signature = |v: u64| reth_rpc_types::Signature {
+            r: reth_primitives::U256::from(1),
+            s: reth_primitives::U256::from(2),
+            v: reth_primitives::U256::from(v),
+            y_parity: None,
+        };
+        let eip1559 = StoredTransaction {
+            tx: Transaction {
+                transaction_type: Some(TxType::Eip1559.into()),
+                signature: Some(signature(1)),
+                ..Default::default()
+            },
+        };
+        let legacy = StoredTransaction {
+            tx: Transaction { transaction_type: Some(0), signature: Some(signature(37)), ..Default::default() },
+        };
+
+        // When
+        let eip1559 = Transaction::from(eip1559);
+        let legacy = Transaction::from(legacy);
+
+        // Then
+        assert_eq!(eip1559.signature.unwrap().y_parity, Some(Parity(true)));
+        assert_eq!(legacy.signature.unwrap().y_parity, None);
+    }
 }
@@ -1,9 +1,12 @@
-use alloy_sol_types::SolType;
+use alloy_sol_types::sol_data;
 use jsonrpsee::types::ErrorObject;
-use reth_primitives::Bytes;
+use reth_primitives::{Address, Bytes};
 use starknet_crypto::FieldElement;
 use thiserror::Error;
 
+use super::utils::decode_call_result;
+use super::utils::keccak::function_selector;
+
 /// List of JSON-RPC error codes from ETH rpc spec.
 /// <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1474.md>
 #[derive(Debug, Copy, PartialEq, Eq, Clone)]
@@ -28,15 +31,22 @@ pub enum EthRpcErrorCode {
 impl From<EthApiError> for EthRpcErrorCode {
     fn from(error: EthApiError) -> Self {
         match error {
-            EthApiError::UnknownBlock | EthApiError::UnknownBlockNumber | EthApiError::TransactionNotFound => {
-                Self::ResourceNotFound
-            }
+            EthApiError::UnknownBlock
+            | EthApiError::UnknownBlockNumber
+            | EthApiError::TransactionNotFound
+            | EthApiError::EarliestBlockNotFound(_) => Self::ResourceNotFound,
             EthApiError::InvalidBlockRange
             | EthApiError::Signature(_)
             | EthApiError::EthereumDataFormat(_)
-            | EthApiError::CalldataExceededLimit(_, _) => Self::InvalidParams,
+            | EthApiError::CalldataExceededLimit(_, _)
+            | EthApiError::FeeComputationOverflow
+            | EthApiError::InvalidParams(_) => Self::InvalidParams,
             EthApiError::Transaction(err) => err.into(),
             EthApiError::Unsupported(_) => Self::InternalError,
+            EthApiError::MethodNotFound(_) => Self::MethodNotFound,
+            EthApiError::QueryLimitExceeded(_) => Self::RequestLimitExceeded,
+            EthApiError::ProviderUnavailable => Self::ResourceUnavailable,
+            EthApiError::Unauthorized(_) => Self::TransactionRejected,
             EthApiError::Kakarot(err) => err.into(),
         }
     }
@@ -54,9 +64,15 @@ pub enum EthApiError {
     /// When a transaction is not found
     #[error("transaction not found")]
     TransactionNotFound,
+    /// When the earliest block is not found
+    #[error("earliest block {0} not found, check the configured earliest block number")]
+    EarliestBlockNotFound(u64),
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// When the parameters of a request are invalid
+    #[error("invalid params: {0}")]
+    InvalidParams(&'static str),
     /// Error related to transaction
     #[error("transaction error: {0}")]
     Transaction(#[from] TransactionError),
@@ -66,6 +82,9 @@ pub enum EthApiError {
     /// Unsupported feature
     #[error("unsupported: {0}")]
     Unsupported(&'static str),
+    /// Unknown or unimplemented method
+    #[error("method not found: {0}")]
+    MethodNotFound(&'static str),
     /// Ethereum data format error
     #[error("ethereum data format error: {0}")]
     EthereumDataFormat(#[from] EthereumDataFormatError),
@@ -75,6 +94,18 @@ pub enum EthApiError {
     /// Error related to transaction calldata being too large.
     #[error("calldata exceeded limit of {0}: {1}")]
     CalldataExceededLimit(u64, u64),
+    /// Error related to an overflow during the fee computation.
+    #[error("fee computation overflow")]
+    FeeComputationOverflow,
+    /// When a query would return more results than allowed.
+    #[error("query returned more than {0} results, try with a narrower block range")]
+    QueryLimitExceeded(u64),
+    /// When the Starknet provider is considered unavailable after repeated failures.
+    #[error("starknet provider unavailable, try again later")]
+    ProviderUnavailable,
+    /// When the sender of a transaction is not allowed to send transactions.
+    #[error("sender {0} is not allowed to send transactions")]
+    Unauthorized(Address),
 }
 
 impl std::fmt::Debug for EthApiError {
@@ -215,10 +246,44 @@ impl From<Vec<FieldElement>> for EvmError {
 }
 
 fn decode_err(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return "execution reverted".to_string();
+    }
+    if bytes.len() < 4 {
+        return format!("{}", bytes.iter().collect::<Bytes>());
+    }
     // Skip the first 4 bytes which is the function selector
-    let msg = &bytes[4..];
-    let maybe_decoded_msg = alloy_sol_types::sol_data::String::abi_decode(msg, true);
-    maybe_decoded_msg.map_or_else(|_| format!("{}", bytes.iter().collect::<Bytes>()), |s| s)
+    let (selector, msg) = bytes.split_at(4);
+    // The `Panic(uint256)` errors are raised by the Solidity compiler checks
+    if selector == function_selector("Panic(uint256)") {
+        if let Ok((code,)) = decode_call_result::<(sol_data::Uint<256>,)>(msg) {
+            return format!("panic: {} ({code:#x})", panic_reason(code.saturating_to()));
+        }
+    }
+    // The `Error(string)` errors are raised by `require` and `revert`
+    if selector == function_selector("Error(string)") {
+        if let Ok((reason,)) = decode_call_result::<(sol_data::String,)>(msg) {
+            return reason;
+        }
+    }
+    format!("{}", bytes.iter().collect::<Bytes>())
+}
+
+/// Returns the reason of a Solidity panic from its code.
+/// <https://docs.soliditylang.org/en/latest/control-structures.html#panic-via-assert-and-error-via-require>
+const fn panic_reason(code: u64) -> &'static str {
+    match code {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic underflow or overflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to a zero-initialized function",
+        _ => "unknown panic",
+    }
 }
 
 /// Error related to a transaction.
@@ -233,6 +298,12 @@ pub enum TransactionError {
     /// Thrown when the gas used overflows u128.
     #[error("gas overflow")]
     GasOverflow,
+    /// Thrown when a request sets both the legacy and the EIP-1559 fee fields.
+    #[error("both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified")]
+    ConflictingFeeFields,
+    /// Thrown when the fee per gas a request is willing to pay is below the base fee.
+    #[error("max fee per gas less than block base fee")]
+    FeeCapTooLow,
     /// Thrown when the transaction isn't the
     /// BlockTransactions::FullTransactions variant.
     #[error("expected full transactions")]
@@ -245,7 +316,10 @@ pub enum TransactionError {
 impl From<TransactionError> for EthRpcErrorCode {
     fn from(error: TransactionError) -> Self {
         match error {
-            TransactionError::InvalidChainId | TransactionError::InvalidTransactionType => Self::InvalidInput,
+            TransactionError::InvalidChainId
+            | TransactionError::InvalidTransactionType
+            | TransactionError::ConflictingFeeFields
+            | TransactionError::FeeCapTooLow => Self::InvalidInput,
             TransactionError::GasOverflow => Self::TransactionRejected,
             TransactionError::ExpectedFullTransactions | TransactionError::Tracing(_) => Self::InternalError,
         }
@@ -267,6 +341,9 @@ pub enum SignatureError {
     /// Thrown when parity is invalid.
     #[error("invalid parity")]
     InvalidParity,
+    /// Thrown when the recovered signer does not match the transaction sender.
+    #[error("recovered signer does not match transaction sender")]
+    SignerMismatch,
 }
 
 /// Error related to Ethereum data format.
@@ -284,6 +361,12 @@ pub enum EthereumDataFormatError {
     /// Error related to starknet to eth conversion or vice versa.
     #[error("primitive conversion error")]
     PrimitiveError,
+    /// Error related to a value which does not fit in a Starknet field element.
+    #[error("value exceeds the Starknet field prime")]
+    FeltOverflow,
+    /// Error related to the decoding of ABI-encoded data.
+    #[error("abi decoding error")]
+    AbiDecodingError,
 }
 
 #[cfg(test)]
@@ -330,4 +413,45 @@ mod tests {
             panic!("Expected EvmError::Other, got {evm_err:?}");
         }
     }
+
+    #[test]
+    fn test_decode_evm_error_panic() {
+        // Given
+        // Panic(0x11), raised by a checked arithmetic underflow
+        let mut bytes = function_selector("Panic(uint256)").to_vec();
+        assert_eq!(bytes, [0x4e, 0x48, 0x7b, 0x71]);
+        bytes.extend([0u8; 31]);
+        bytes.push(0x11);
+        let bytes: Vec<_> = bytes.into_iter().map(FieldElement::from).collect();
+
+        // When
+        let evm_err: EvmError = bytes.into();
+
+        // Then
+        assert_eq!(evm_err.to_string(), "panic: arithmetic underflow or overflow (0x11)");
+    }
+
+    #[test]
+    fn test_decode_evm_error_truncated() {
+        // Given
+        let short_selector: Vec<_> = [0x4e_u8, 0x48].into_iter().map(FieldElement::from).collect();
+        let short_panic: Vec<_> = [0x4e_u8, 0x48, 0x7b, 0x71, 0x00, 0x11].into_iter().map(FieldElement::from).collect();
+
+        // When
+        let short_selector: EvmError = short_selector.into();
+        let short_panic: EvmError = short_panic.into();
+
+        // Then
+        assert_eq!(short_selector.to_string(), "0x4e48");
+        assert_eq!(short_panic.to_string(), "0x4e487b710011");
+    }
+
+    #[test]
+    fn test_decode_evm_error_empty() {
+        // When
+        let evm_err: EvmError = Vec::<FieldElement>::new().into();
+
+        // Then
+        assert_eq!(evm_err.to_string(), "execution reverted");
+    }
 }
@@ -0,0 +1,250 @@
+use reth_primitives::U256;
+use reth_rpc_types::TransactionRequest;
+
+use super::error::{EthApiError, TransactionError};
+
+/// Computes the effective gas price of a transaction given the base fee, the maximum
+/// fee per gas and the optional maximum priority fee per gas, i.e.
+/// `min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)`.
+/// Returns an error instead of overflowing.
+#[inline]
+pub(crate) fn effective_gas_price(
+    base_fee: U256,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: Option<U256>,
+) -> Result<U256, EthApiError> {
+    let Some(max_priority_fee_per_gas) = max_priority_fee_per_gas else {
+        return Ok(max_fee_per_gas);
+    };
+    let gas_price = base_fee.checked_add(max_priority_fee_per_gas).ok_or(EthApiError::FeeComputationOverflow)?;
+    Ok(gas_price.min(max_fee_per_gas))
+}
+
+/// Returns the request of a call made under the given base fee: the call pays the gas price
+/// it would pay in a block with this base fee, set as its legacy gas price. Returns an error
+/// if the fee per gas of the request is below the base fee.
+pub(crate) fn call_request_with_base_fee(
+    request: TransactionRequest,
+    base_fee: U256,
+) -> Result<TransactionRequest, EthApiError> {
+    // The conflicting fee fields are rejected when the call input is prepared
+    if request.gas_price.is_some() && (request.max_fee_per_gas.is_some() || request.max_priority_fee_per_gas.is_some())
+    {
+        return Ok(request);
+    }
+
+    let max_priority_fee_per_gas = request.max_priority_fee_per_gas.map(U256::from);
+    let gas_price = match (request.gas_price, request.max_fee_per_gas) {
+        (Some(gas_price), _) => U256::from(gas_price),
+        (None, Some(max_fee_per_gas)) => {
+            effective_gas_price(base_fee, U256::from(max_fee_per_gas), max_priority_fee_per_gas)?
+        }
+        (None, None) => base_fee
+            .checked_add(max_priority_fee_per_gas.unwrap_or_default())
+            .ok_or(EthApiError::FeeComputationOverflow)?,
+    };
+    if gas_price < base_fee {
+        return Err(TransactionError::FeeCapTooLow.into());
+    }
+
+    Ok(TransactionRequest {
+        gas_price: Some(gas_price.try_into().map_err(|_| TransactionError::GasOverflow)?),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        ..request
+    })
+}
+
+/// Computes the fees of a transaction given its gas price and gas limit.
+/// Returns an error instead of overflowing.
+#[inline]
+pub(crate) fn transaction_fees(gas_price: U256, gas_limit: U256) -> Result<U256, EthApiError> {
+    gas_price.checked_mul(gas_limit).ok_or(EthApiError::FeeComputationOverflow)
+}
+
+/// Converts an EVM fee, in wei, into Starknet fee units given the number of Starknet
+/// fee units a wei is worth. Returns an error instead of overflowing.
+#[inline]
+pub fn eth_to_starknet_fee(eth_fee: U256, starknet_fee_ratio: u64) -> Result<U256, EthApiError> {
+    eth_fee.checked_mul(U256::from(starknet_fee_ratio)).ok_or(EthApiError::FeeComputationOverflow)
+}
+
+/// Converts a fee in Starknet fee units into its EVM equivalent, in wei, given the number
+/// of Starknet fee units a wei is worth. The result is rounded down.
+#[inline]
+pub fn starknet_to_eth_fee(starknet_fee: U256, starknet_fee_ratio: u64) -> U256 {
+    starknet_fee / U256::from(starknet_fee_ratio.max(1))
+}
+
+/// Converts a fee in Starknet fee units into the EVM gas it pays for at the given gas price.
+/// Returns zero for a zero gas price.
+#[inline]
+pub fn starknet_fee_to_gas(starknet_fee: U256, gas_price: U256, starknet_fee_ratio: u64) -> U256 {
+    starknet_to_eth_fee(starknet_fee, starknet_fee_ratio).checked_div(gas_price).unwrap_or_default()
+}
+
+/// Number of decimals of the wei, i.e. of the EVM native currency.
+const WEI_DECIMALS: u8 = 18;
+
+/// Returns the power of ten scaling an amount between the wei and a fee token with the
+/// given number of decimals, with whether the fee token has more decimals than the wei.
+fn fee_token_scale(decimals: u8) -> Result<(U256, bool), EthApiError> {
+    let exponent = decimals.abs_diff(WEI_DECIMALS);
+    let scale = U256::from(10).checked_pow(U256::from(exponent)).ok_or(EthApiError::FeeComputationOverflow)?;
+    Ok((scale, decimals > WEI_DECIMALS))
+}
+
+/// Converts an amount in wei into an amount of the fee token with the given number of decimals.
+/// The result is rounded down. Returns an error instead of overflowing.
+#[inline]
+pub fn wei_to_fee_token(wei: U256, decimals: u8) -> Result<U256, EthApiError> {
+    match fee_token_scale(decimals)? {
+        (scale, true) => wei.checked_mul(scale).ok_or(EthApiError::FeeComputationOverflow),
+        (scale, false) => Ok(wei / scale),
+    }
+}
+
+/// Converts an amount of the fee token with the given number of decimals into an amount in wei.
+/// The result is rounded down. Returns an error instead of overflowing.
+#[inline]
+pub fn fee_token_to_wei(amount: U256, decimals: u8) -> Result<U256, EthApiError> {
+    match fee_token_scale(decimals)? {
+        (scale, true) => Ok(amount / scale),
+        (scale, false) => amount.checked_mul(scale).ok_or(EthApiError::FeeComputationOverflow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_gas_price() {
+        // Given
+        let base_fee = U256::from(10);
+        let max_fee_per_gas = U256::from(15);
+
+        // When
+        let gas_price_with_tip = effective_gas_price(base_fee, max_fee_per_gas, Some(U256::from(2))).unwrap();
+        let gas_price_capped = effective_gas_price(base_fee, max_fee_per_gas, Some(U256::from(10))).unwrap();
+        let gas_price_legacy = effective_gas_price(base_fee, max_fee_per_gas, None).unwrap();
+
+        // Then
+        assert_eq!(gas_price_with_tip, U256::from(12));
+        assert_eq!(gas_price_capped, max_fee_per_gas);
+        assert_eq!(gas_price_legacy, max_fee_per_gas);
+    }
+
+    #[test]
+    fn test_fee_computation_overflow() {
+        // Given
+        let near_max = U256::MAX - U256::from(1);
+
+        // When
+        let gas_price = effective_gas_price(near_max, U256::MAX, Some(U256::from(2)));
+        let fees = transaction_fees(near_max, U256::from(2));
+
+        // Then
+        assert!(matches!(gas_price, Err(EthApiError::FeeComputationOverflow)));
+        assert!(matches!(fees, Err(EthApiError::FeeComputationOverflow)));
+    }
+
+    #[test]
+    fn test_starknet_fee_to_gas() {
+        // Given
+        let gas_price = U256::from(10);
+        let gas = U256::from(21_000);
+        let starknet_fee_ratio = 1_000;
+        let starknet_fee = U256::from(210_000_000);
+
+        // When
+        let eth_fee = starknet_to_eth_fee(starknet_fee, starknet_fee_ratio);
+        let converted_gas = starknet_fee_to_gas(starknet_fee, gas_price, starknet_fee_ratio);
+
+        // Then
+        assert_eq!(eth_fee, gas * gas_price);
+        assert_eq!(converted_gas, gas);
+        assert_eq!(eth_to_starknet_fee(eth_fee, starknet_fee_ratio).unwrap(), starknet_fee);
+        assert_eq!(starknet_fee_to_gas(starknet_fee, U256::ZERO, starknet_fee_ratio), U256::ZERO);
+    }
+
+    #[test]
+    fn test_eth_to_starknet_fee_overflow() {
+        // When
+        let fee = eth_to_starknet_fee(U256::MAX, 2);
+
+        // Then
+        assert!(matches!(fee, Err(EthApiError::FeeComputationOverflow)));
+    }
+
+    #[test]
+    fn test_fee_token_18_decimals() {
+        // Given
+        let amount = U256::from(123_456_789);
+
+        // When
+        let fee_token = wei_to_fee_token(amount, 18).unwrap();
+        let wei = fee_token_to_wei(amount, 18).unwrap();
+
+        // Then
+        assert_eq!(fee_token, amount);
+        assert_eq!(wei, amount);
+    }
+
+    #[test]
+    fn test_fee_token_other_decimals() {
+        // Given
+        // 1.5 token with 6 decimals, e.g. USDC
+        let usdc = U256::from(1_500_000);
+        let wei = U256::from(1_500_000_000_000_000_000_u128);
+
+        // When
+        let usdc_to_wei = fee_token_to_wei(usdc, 6).unwrap();
+        let wei_to_usdc = wei_to_fee_token(wei + U256::from(1), 6).unwrap();
+        let wei_to_24_decimals = wei_to_fee_token(wei, 24).unwrap();
+
+        // Then
+        assert_eq!(usdc_to_wei, wei);
+        assert_eq!(wei_to_usdc, usdc);
+        assert_eq!(wei_to_24_decimals, wei * U256::from(1_000_000));
+        assert_eq!(fee_token_to_wei(wei_to_24_decimals, 24).unwrap(), wei);
+        assert!(matches!(fee_token_to_wei(U256::MAX, 6), Err(EthApiError::FeeComputationOverflow)));
+        assert!(matches!(wei_to_fee_token(U256::from(1), 255), Err(EthApiError::FeeComputationOverflow)));
+    }
+
+    #[test]
+    fn test_call_request_with_base_fee() {
+        // Given
+        let base_fee = U256::from(10);
+        let eip1559 =
+            TransactionRequest { max_fee_per_gas: Some(15), max_priority_fee_per_gas: Some(2), ..Default::default() };
+        let legacy = TransactionRequest { gas_price: Some(12), ..Default::default() };
+
+        // When
+        let eip1559 = call_request_with_base_fee(eip1559, base_fee).unwrap();
+        let legacy = call_request_with_base_fee(legacy, base_fee).unwrap();
+        let default = call_request_with_base_fee(TransactionRequest::default(), base_fee).unwrap();
+
+        // Then
+        assert_eq!(eip1559.gas_price, Some(12));
+        assert_eq!(eip1559.max_fee_per_gas, None);
+        assert_eq!(eip1559.max_priority_fee_per_gas, None);
+        assert_eq!(legacy.gas_price, Some(12));
+        assert_eq!(default.gas_price, Some(10));
+    }
+
+    #[test]
+    fn test_call_request_with_base_fee_too_high() {
+        // Given
+        let eip1559 = TransactionRequest { max_fee_per_gas: Some(15), ..Default::default() };
+        let legacy = TransactionRequest { gas_price: Some(15), ..Default::default() };
+
+        // When
+        let eip1559 = call_request_with_base_fee(eip1559, U256::from(16));
+        let legacy = call_request_with_base_fee(legacy, U256::from(16));
+
+        // Then
+        assert!(matches!(eip1559, Err(EthApiError::Transaction(TransactionError::FeeCapTooLow))));
+        assert!(matches!(legacy, Err(EthApiError::Transaction(TransactionError::FeeCapTooLow))));
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+use reth_primitives::{Address, B256};
+use reth_rpc_types::{Filter, FilteredParams, Log, ValueOrArray};
+
+/// Logs of an indexed block, keyed by the address emitting them.
+#[derive(Debug, Default)]
+struct IndexedBlock {
+    hash: B256,
+    logs: HashMap<Address, Vec<Log>>,
+}
+
+/// In-memory index of the logs of the most recent blocks, keyed by block number and
+/// by the address emitting the logs. The index holds at most `max_blocks` blocks, the
+/// oldest blocks being evicted first.
+#[derive(Debug)]
+pub struct LogIndex {
+    max_blocks: u64,
+    blocks: RwLock<BTreeMap<u64, IndexedBlock>>,
+}
+
+impl LogIndex {
+    pub fn new(max_blocks: u64) -> Self {
+        Self { max_blocks, blocks: RwLock::default() }
+    }
+
+    /// Returns the maximum number of blocks held by the index.
+    pub const fn max_blocks(&self) -> u64 {
+        self.max_blocks
+    }
+
+    /// Returns the logs of the blocks `from..=to` matching the address and topics of the filter,
+    /// ordered by block number and log index. Returns `None` if one of the blocks is not indexed.
+    pub fn get(&self, filter: &Filter, from: u64, to: u64) -> Option<Vec<Log>> {
+        let blocks = self.blocks.read().ok()?;
+        let addresses = filter.address.to_value_or_array().map(|addresses| match addresses {
+            ValueOrArray::Value(address) => vec![address],
+            ValueOrArray::Array(addresses) => addresses,
+        });
+        let params = FilteredParams::new(Some(filter.clone()));
+
+        let mut logs = Vec::new();
+        for number in from..=to {
+            let block = &blocks.get(&number)?.logs;
+            let candidates: Box<dyn Iterator<Item = &Log>> = match &addresses {
+                Some(addresses) => Box::new(addresses.iter().filter_map(|address| block.get(address)).flatten()),
+                None => Box::new(block.values().flatten()),
+            };
+            logs.extend(candidates.filter(|log| params.filter_topics(log.topics())).cloned());
+        }
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+        Some(logs)
+    }
+
+    /// Drops the indexed blocks whose hash differs from the hash of the given headers, e.g.
+    /// after a reorg, and returns the headers of the blocks which are not indexed.
+    pub fn sync(&self, headers: &[(u64, B256)]) -> Vec<(u64, B256)> {
+        let Ok(mut blocks) = self.blocks.write() else { return headers.to_vec() };
+        headers
+            .iter()
+            .filter(|(number, hash)| match blocks.get(number) {
+                Some(block) if block.hash == *hash => false,
+                Some(_) => {
+                    blocks.remove(number);
+                    true
+                }
+                None => true,
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Indexes the logs of the given blocks, which must hold all the logs of these blocks.
+    /// The oldest blocks are evicted once the index holds more than `max_blocks` blocks.
+    pub fn insert(&self, headers: &[(u64, B256)], logs: Vec<Log>) {
+        let Ok(mut blocks) = self.blocks.write() else { return };
+        for (number, hash) in headers {
+            blocks.insert(*number, IndexedBlock { hash: *hash, logs: HashMap::new() });
+        }
+        for log in logs {
+            let Some(block) = log.block_number.and_then(|number| blocks.get_mut(&number)) else { continue };
+            if log.block_hash == Some(block.hash) {
+                block.logs.entry(log.address()).or_default().push(log);
+            }
+        }
+        while blocks.len() as u64 > self.max_blocks {
+            blocks.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+
+    fn log(block_number: u64, log_index: u64, address: Address, topic: B256) -> Log {
+        Log {
+            inner: reth_primitives::Log::new_unchecked(address, vec![topic], Bytes::default()),
+            block_hash: Some(hash(block_number)),
+            block_number: Some(block_number),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    fn hash(block_number: u64) -> B256 {
+        B256::with_last_byte(u8::try_from(block_number).unwrap())
+    }
+
+    fn headers(blocks: std::ops::RangeInclusive<u64>) -> Vec<(u64, B256)> {
+        blocks.map(|number| (number, hash(number))).collect()
+    }
+
+    #[test]
+    fn test_log_index() {
+        // Given
+        let index = LogIndex::new(2);
+        let (first, second) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let topic = B256::repeat_byte(3);
+        let logs = vec![log(2, 1, second, topic), log(1, 0, first, topic), log(2, 0, first, B256::ZERO)];
+
+        // When
+        index.insert(&headers(1..=2), logs);
+
+        // Then
+        let all = index.get(&Filter::new(), 1, 2).unwrap();
+        assert_eq!(
+            all.iter().map(|log| (log.block_number, log.log_index)).collect::<Vec<_>>(),
+            [(Some(1), Some(0)), (Some(2), Some(0)), (Some(2), Some(1))]
+        );
+        assert_eq!(index.get(&Filter::new().address(first), 1, 2).unwrap().len(), 2);
+        assert_eq!(index.get(&Filter::new().address(first).event_signature(topic), 1, 2).unwrap().len(), 1);
+        assert!(index.get(&Filter::new(), 0, 2).is_none());
+    }
+
+    #[test]
+    fn test_log_index_eviction() {
+        // Given
+        let index = LogIndex::new(2);
+
+        // When
+        index.insert(&headers(1..=3), vec![]);
+
+        // Then
+        assert!(index.get(&Filter::new(), 1, 1).is_none());
+        assert_eq!(index.get(&Filter::new(), 2, 3), Some(vec![]));
+    }
+
+    #[test]
+    fn test_log_index_sync() {
+        // Given
+        let index = LogIndex::new(8);
+        let address = Address::repeat_byte(1);
+        index.insert(&headers(1..=2), vec![log(1, 0, address, B256::ZERO), log(2, 0, address, B256::ZERO)]);
+        let reorged = (2, B256::repeat_byte(0xff));
+
+        // When
+        let missing = index.sync(&[(1, hash(1)), reorged, (3, hash(3))]);
+
+        // Then
+        // The reorged block is dropped and reported as missing along with the new block
+        assert_eq!(missing, vec![reorged, (3, hash(3))]);
+        assert_eq!(index.get(&Filter::new(), 1, 1).unwrap().len(), 1);
+        assert!(index.get(&Filter::new(), 2, 2).is_none());
+    }
+}
@@ -1,8 +1,16 @@
+pub mod block_cache;
+pub mod block_stream;
+pub mod circuit_breaker;
+pub mod config;
 pub mod constant;
 pub mod contracts;
 pub mod database;
 pub mod error;
+pub mod gas;
+#[cfg(feature = "log-index")]
+pub mod log_index;
 pub mod pending_pool;
 pub mod provider;
+pub mod scan_cache;
 pub mod starknet;
 pub mod utils;
@@ -1,36 +1,47 @@
-use alloy_rlp::{Decodable, Encodable};
+use alloy_rlp::Decodable;
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use cainome::cairo_serde::CairoArrayLegacy;
 use eyre::Result;
+use futures::StreamExt;
 use itertools::Itertools;
-use mongodb::bson::doc;
-use reth_primitives::constants::EMPTY_ROOT_HASH;
+use mongodb::bson::{doc, Document};
 use reth_primitives::{
-    Address, BlockId, BlockNumberOrTag, Bytes, TransactionSigned, TransactionSignedEcRecovered, TxKind, B256, U256, U64,
+    Address, BlockId, BlockNumberOrTag, Bytes, TransactionSigned, TransactionSignedEcRecovered, TxKind, B256,
+    KECCAK_EMPTY, U256, U64,
 };
 use reth_rpc_types::serde_helpers::JsonStorageKey;
 use reth_rpc_types::txpool::TxpoolContent;
 use reth_rpc_types::{
-    Block, BlockHashOrNumber, BlockTransactions, FeeHistory, Filter, FilterChanges, Header, Index, RichBlock,
-    Transaction, TransactionReceipt, TransactionRequest, ValueOrArray,
+    BlockHashOrNumber, BlockTransactions, FeeHistory, Filter, FilterChanges, Header, Index, RichBlock, Transaction,
+    TransactionReceipt, TransactionRequest, ValueOrArray,
 };
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_rpc_types_compat::transaction::from_recovered;
-use starknet::core::types::SyncStatusType;
+use starknet::core::types::{
+    BlockTag, MaybePendingBlockWithTxs, MaybePendingTransactionReceipt, StarknetError, SyncStatusType,
+};
 use starknet::core::utils::get_storage_var_address;
 use starknet_crypto::FieldElement;
 
+use super::block_cache::BlockCache;
+use super::config::KakarotConfig;
 use super::constant::{
-    ADDRESS_HEX_STRING_LEN, BLOCK_NUMBER_HEX_STRING_LEN, CALL_REQUEST_GAS_LIMIT, HASH_HEX_STRING_LEN,
-    TRANSACTION_MAX_RETRIES, U64_HEX_STRING_LEN,
+    ADDRESS_HEX_STRING_LEN, BATCH_CONCURRENCY, BLOCK_NUMBER_HEX_STRING_LEN, CALL_REQUEST_GAS_LIMIT,
+    HASH_HEX_STRING_LEN, LOGS_BLOOM_FILTER_MAX_BLOCKS, LOGS_TOPICS_HEX_STRING_LEN, MAX_PRIORITY_FEE_PER_GAS,
+    TRANSACTION_MAX_RETRIES, TRANSACTION_SCAN_MISSES, U64_HEX_STRING_LEN,
 };
 use super::database::types::{
-    header::StoredHeader, log::StoredLog, receipt::StoredTransactionReceipt, transaction::StoredPendingTransaction,
-    transaction::StoredTransaction, transaction::StoredTransactionHash,
+    header::StoredHeader, header::StoredHeaderBloom, log::StoredLog, receipt::StoredTransactionReceipt,
+    transaction::StoredPendingTransaction, transaction::StoredStarknetTransactionHash, transaction::StoredTransaction,
+    transaction::StoredTransactionHash,
 };
 use super::database::{CollectionName, Database};
 use super::error::{EthApiError, EthereumDataFormatError, EvmError, KakarotError, SignatureError, TransactionError};
+use super::gas::{effective_gas_price, eth_to_starknet_fee, fee_token_to_wei, transaction_fees};
+#[cfg(feature = "log-index")]
+use super::log_index::LogIndex;
+use super::scan_cache::ScanMissCache;
 use super::starknet::kakarot_core::WHITE_LISTED_EIP_155_TRANSACTION_HASHES;
 use super::starknet::kakarot_core::{
     self,
@@ -40,12 +51,24 @@ use super::starknet::kakarot_core::{
     starknet_address, to_starknet_transaction, KAKAROT_ADDRESS,
 };
 use super::starknet::{ERC20Reader, STARKNET_NATIVE_TOKEN};
-use super::utils::{contract_not_found, entrypoint_not_found, into_filter, split_u256, to_logs_filter};
+use super::utils::keccak::keccak256;
+use super::utils::{
+    accumulate_gas_used, bloom_matches_filter, check_timestamp, clamp_pending_timestamp, contract_not_found,
+    dedup_pending_logs, entrypoint_not_found, fill_contract_address, into_filter, is_unfiltered, join_u256, set_status,
+    split_u256, to_logs_filter,
+};
 use crate::eth_provider::utils::format_hex;
-use crate::models::block::{EthBlockId, EthBlockNumberOrTag};
+use crate::models::block::{rpc_block, EthBlockId, EthBlockNumberOrTag};
 use crate::models::felt::Felt252Wrapper;
+use crate::models::log::{LogCursor, PaginatedLogs};
+use crate::models::receipt::{
+    approximate_gas_used, deployed_contract_address, execution_result_to_status, invoke_actual_fee, invoke_events,
+};
+use crate::models::transaction::{filter_starknet_into_eth_txs, is_kakarot_transaction, starknet_to_rpc_transaction};
 use crate::tracing::builder::TRACING_BLOCK_GAS_LIMIT;
 use crate::{into_via_try_wrapper, into_via_wrapper};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 pub type EthProviderResult<T> = Result<T, EthApiError>;
 
@@ -105,8 +128,18 @@ pub trait EthereumProvider {
     async fn transaction_count(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<U256>;
     /// Returns the code for the address at the given block.
     async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<Bytes>;
+    /// Returns the keccak hash of the code of an address, `KECCAK_EMPTY` for an address without code.
+    async fn code_hash(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<B256>;
     /// Returns the logs for the given filter.
     async fn get_logs(&self, filter: Filter) -> EthProviderResult<FilterChanges>;
+    /// Returns at most `page_size` logs matching the filter, starting after the cursor if any.
+    /// The page size is capped to the maximum number of logs of a query, and must not be zero.
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        page_size: u64,
+    ) -> EthProviderResult<PaginatedLogs>;
     /// Returns the result of a call.
     async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>) -> EthProviderResult<Bytes>;
     /// Returns the result of a estimate gas.
@@ -122,6 +155,8 @@ pub trait EthereumProvider {
     async fn send_raw_transaction(&self, transaction: Bytes) -> EthProviderResult<B256>;
     /// Returns the current gas price.
     async fn gas_price(&self) -> EthProviderResult<U256>;
+    /// Returns the current max priority fee per gas.
+    async fn max_priority_fee_per_gas(&self) -> EthProviderResult<U256>;
     /// Returns the block receipts for a block.
     async fn block_receipts(&self, block_id: Option<BlockId>) -> EthProviderResult<Option<Vec<TransactionReceipt>>>;
     /// Returns the transactions for a block.
@@ -133,6 +168,11 @@ pub trait EthereumProvider {
     async fn txpool_transactions(&self) -> EthProviderResult<Vec<Transaction>>;
     /// Returns the content of the pending pool.
     async fn txpool_content(&self) -> EthProviderResult<TxpoolContent>;
+    /// Returns the Starknet block with its transactions, without any conversion.
+    async fn starknet_block(&self, block_id: Option<BlockId>) -> EthProviderResult<MaybePendingBlockWithTxs>;
+    /// Returns the hash of the Starknet transaction carrying the given Ethereum transaction,
+    /// if it was sent through the RPC.
+    async fn starknet_transaction_hash(&self, hash: B256) -> EthProviderResult<Option<B256>>;
 }
 
 /// Structure that implements the `EthereumProvider` trait.
@@ -143,6 +183,12 @@ pub struct EthDataProvider<SP: starknet::providers::Provider> {
     database: Database,
     starknet_provider: SP,
     chain_id: u64,
+    kakarot_address: FieldElement,
+    config: KakarotConfig,
+    block_cache: Option<Arc<BlockCache>>,
+    scan_misses: Arc<ScanMissCache>,
+    #[cfg(feature = "log-index")]
+    log_index: Option<Arc<LogIndex>>,
 }
 
 impl<SP> EthDataProvider<SP>
@@ -153,6 +199,30 @@ where
     pub const fn database(&self) -> &Database {
         &self.database
     }
+
+    /// Returns the address of the Kakarot contract.
+    pub const fn kakarot_address(&self) -> FieldElement {
+        self.kakarot_address
+    }
+
+    /// Returns the configuration of the provider.
+    pub const fn config(&self) -> &KakarotConfig {
+        &self.config
+    }
+
+    /// Sets the configuration of the provider, resetting the in-memory caches which depend on it.
+    pub fn with_config(mut self, config: KakarotConfig) -> Result<Self> {
+        config.validate()?;
+        self.block_cache = (config.block_cache_size > 0).then(|| Arc::new(BlockCache::new(config.block_cache_size)));
+        // The misses of the previous scan depth don't hold for the new one
+        self.scan_misses = Arc::new(ScanMissCache::new(TRANSACTION_SCAN_MISSES));
+        #[cfg(feature = "log-index")]
+        {
+            self.log_index = (config.log_index_blocks > 0).then(|| Arc::new(LogIndex::new(config.log_index_blocks)));
+        }
+        self.config = config;
+        Ok(self)
+    }
 }
 
 #[async_trait]
@@ -166,7 +236,9 @@ where
             BlockId::Number(number_or_tag) => self.tag_into_block_number(*number_or_tag).await?.to::<u64>().into(),
         };
 
-        Ok(self.header(block).await?.map(|h| h.header))
+        let Some(mut header) = self.header(block).await?.map(|h| h.header) else { return Ok(None) };
+        self.serve_timestamp(&mut header).await?;
+        Ok(Some(header))
     }
 
     async fn block_number(&self) -> EthProviderResult<U64> {
@@ -209,7 +281,12 @@ where
         full: bool,
     ) -> EthProviderResult<Option<RichBlock>> {
         let block_number = self.tag_into_block_number(number_or_tag).await?;
-        Ok(self.block(block_number.into(), full).await?)
+        let block = self.block(block_number.into(), full).await?;
+        // The earliest block should always exist, its absence means a misconfiguration
+        if number_or_tag == BlockNumberOrTag::Earliest && block.is_none() {
+            return Err(EthApiError::EarliestBlockNotFound(self.config.earliest_block_number));
+        }
+        Ok(block)
     }
 
     async fn block_transaction_count_by_hash(&self, hash: B256) -> EthProviderResult<Option<U256>> {
@@ -270,7 +347,10 @@ where
             },
         ];
 
-        Ok(self.database.get_one_aggregate::<StoredTransaction>(pipeline).await?.map(Into::into))
+        match self.database.get_one_aggregate::<StoredTransaction>(pipeline).await? {
+            Some(transaction) => Ok(Some(transaction.into())),
+            None => self.scan_transaction_by_hash(hash).await,
+        }
     }
 
     async fn transaction_by_block_hash_and_index(
@@ -299,14 +379,31 @@ where
     }
 
     async fn transaction_receipt(&self, hash: B256) -> EthProviderResult<Option<TransactionReceipt>> {
-        Ok(self
+        let receipt = self
             .database
             .get_one::<StoredTransactionReceipt>(
                 into_filter("receipt.transactionHash", &hash, HASH_HEX_STRING_LEN),
                 None,
             )
-            .await?
-            .map(Into::into))
+            .await?;
+        let Some(receipt) = receipt else { return Ok(None) };
+        let receipt: TransactionReceipt = receipt.into();
+
+        // The cumulative gas used depends on the receipts of the preceding transactions of the block,
+        // so it is computed from the receipts of the whole block, as for the block receipts.
+        let receipts = match receipt.block_hash {
+            Some(block_hash) => {
+                let filter = into_filter("receipt.blockHash", &block_hash, HASH_HEX_STRING_LEN);
+                self.receipts_with_gas_used(filter).await?
+            }
+            None => {
+                let mut receipts = self.with_approximate_gas_used(vec![receipt]).await?;
+                accumulate_gas_used(&mut receipts);
+                receipts
+            }
+        };
+        let receipts = receipts.into_iter().filter(|receipt| receipt.transaction_hash == hash).take(1).collect();
+        Ok(self.with_contract_addresses(receipts).await?.pop())
     }
 
     async fn balance(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<U256> {
@@ -324,7 +421,7 @@ where
 
         let low: U256 = into_via_wrapper!(balance.low);
         let high: U256 = into_via_wrapper!(balance.high);
-        Ok(low + (high << 128))
+        fee_token_to_wei(low + (high << 128), self.config.fee_token_decimals)
     }
 
     async fn storage_at(
@@ -350,30 +447,44 @@ where
         let storage = maybe_storage.map_err(KakarotError::from)?.value;
         let low: U256 = into_via_wrapper!(storage.low);
         let high: U256 = into_via_wrapper!(storage.high);
-        let storage: U256 = low + (high << 128);
+        let storage = join_u256(low, high);
 
         Ok(storage.into())
     }
 
     async fn transaction_count(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<U256> {
-        let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        // The latest tag should not account for the transactions of the pending block.
+        let starknet_block_id = match block_id {
+            Some(BlockId::Number(BlockNumberOrTag::Latest)) => starknet::core::types::BlockId::Tag(BlockTag::Latest),
+            _ => self.to_starknet_block_id(block_id).await?,
+        };
 
-        let address = starknet_address(address);
-        let account_contract = AccountContractReader::new(address, &self.starknet_provider);
+        let starknet_address = starknet_address(address);
+        let account_contract = AccountContractReader::new(starknet_address, &self.starknet_provider);
         let maybe_nonce = account_contract.get_nonce().block_id(starknet_block_id).call().await;
 
-        if contract_not_found(&maybe_nonce) {
-            return Ok(U256::ZERO);
-        }
-        let nonce = maybe_nonce.map_err(KakarotError::from)?.nonce;
+        let nonce = if contract_not_found(&maybe_nonce) {
+            U256::ZERO
+        } else {
+            let nonce = maybe_nonce.map_err(KakarotError::from)?.nonce;
+
+            // Get the protocol nonce as well, in edge cases where the protocol nonce is higher than the account nonce.
+            // This can happen when an underlying Starknet transaction reverts => Account storage changes are reverted,
+            // but the protocol nonce is still incremented.
+            let protocol_nonce =
+                self.starknet_provider.get_nonce(starknet_block_id, starknet_address).await.unwrap_or_default();
+            into_via_wrapper!(nonce.max(protocol_nonce))
+        };
 
-        // Get the protocol nonce as well, in edge cases where the protocol nonce is higher than the account nonce.
-        // This can happen when an underlying Starknet transaction reverts => Account storage changes are reverted,
-        // but the protocol nonce is still incremented.
-        let protocol_nonce = self.starknet_provider.get_nonce(starknet_block_id, address).await.unwrap_or_default();
-        let nonce = nonce.max(protocol_nonce);
+        // The pending tag also accounts for the transactions of the address which are still in the pending pool.
+        if block_id == Some(BlockId::Number(BlockNumberOrTag::Pending)) {
+            let filter = into_filter("tx.from", &address, ADDRESS_HEX_STRING_LEN);
+            let pending_transactions: Vec<StoredPendingTransaction> = self.database.get(filter, None).await?;
+            let pending_nonce = pending_transactions.iter().map(|tx| U256::from(tx.tx.nonce) + U256::from(1)).max();
+            return Ok(pending_nonce.map_or(nonce, |pending_nonce| nonce.max(pending_nonce)));
+        }
 
-        Ok(into_via_wrapper!(nonce))
+        Ok(nonce)
     }
 
     async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<Bytes> {
@@ -392,53 +503,108 @@ where
         Ok(Bytes::from(bytecode.into_iter().filter_map(|x| x.try_into().ok()).collect::<Vec<_>>()))
     }
 
+    async fn code_hash(&self, address: Address, block_id: Option<BlockId>) -> EthProviderResult<B256> {
+        let code = self.get_code(address, block_id).await?;
+        Ok(if code.is_empty() { KECCAK_EMPTY } else { keccak256(&code) })
+    }
+
     async fn get_logs(&self, filter: Filter) -> EthProviderResult<FilterChanges> {
-        let current_block = self.block_number().await?.try_into().map_err(|_| EthApiError::UnknownBlockNumber)?;
-        let block_hash = filter.get_block_hash();
+        let range = match filter.get_block_hash() {
+            Some(_) => None,
+            None => match self.logs_block_range(&filter).await? {
+                Some(range) => Some(range),
+                None => return Ok(FilterChanges::Empty),
+            },
+        };
 
-        // Create the database filter.
-        let mut database_filter = if block_hash.is_some() {
-            // We filter by block hash on matching the exact block hash.
-            doc! {
-                "log.blockHash": format_hex(block_hash.unwrap(), HASH_HEX_STRING_LEN)
+        #[cfg(feature = "log-index")]
+        if let Some((from, to)) = range {
+            if let Some(logs) = self.indexed_logs(&filter, from, to).await? {
+                return Ok(FilterChanges::Logs(logs));
             }
-        } else {
-            let from = filter.get_from_block().unwrap_or_default();
-            let to = filter.get_to_block().unwrap_or(current_block);
+        }
 
-            let (from, to) = match (from, to) {
-                (from, to) if from > current_block || to < from => return Ok(FilterChanges::Empty),
-                (from, to) if to > current_block => (from, current_block),
-                other => other,
-            };
-            // We filter by block number using $gte and $lte.
-            doc! {
-                "log.blockNumber": {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
-            }
+        let Some(database_filter) = self.logs_database_filter(&filter, range).await? else {
+            return Ok(FilterChanges::Empty);
         };
 
-        // TODO: this will work for now but isn't very efficient. Would need to:
-        // 1. Create the bloom filter from the topics
-        // 2. Query the database for logs within block range with the bloom filter
-        // 3. Filter this reduced set of logs by the topics
-        // 4. Limit the number of logs returned
+        // Bail early if the query would return more logs than allowed
+        if let Some(max_logs) = self.config.max_logs {
+            let count = self.database.count::<StoredLog>(database_filter.clone()).await?;
+            if count > max_logs {
+                return Err(EthApiError::QueryLimitExceeded(max_logs));
+            }
+        }
 
-        // Convert the topics to a MongoDB filter and add it to the database filter
-        let logs_filter = to_logs_filter(&filter.topics);
-        database_filter.extend(logs_filter);
+        let mut logs = self.database.get_and_map_to::<_, StoredLog>(database_filter, None).await?;
+        dedup_pending_logs(&mut logs);
 
-        // Add the address filter if any
-        if let Some(addresses) = filter.address.to_value_or_array().map(|a| match a {
-            ValueOrArray::Value(address) => vec![address],
-            ValueOrArray::Array(addresses) => addresses,
-        }) {
-            database_filter.insert(
-                "log.address",
-                doc! {"$in": addresses.into_iter().map(|a| format_hex(a, ADDRESS_HEX_STRING_LEN)).collect::<Vec<_>>()},
-            );
+        Ok(FilterChanges::Logs(logs))
+    }
+
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        page_size: u64,
+    ) -> EthProviderResult<PaginatedLogs> {
+        if page_size == 0 {
+            return Err(EthApiError::InvalidParams("page size must be greater than zero"));
         }
+        let page_size = self.config.max_logs.map_or(page_size, |max_logs| page_size.min(max_logs.max(1)));
 
-        Ok(FilterChanges::Logs(self.database.get_and_map_to::<_, StoredLog>(database_filter, None).await?))
+        // Skip the blocks before the cursor
+        let filter = match cursor {
+            Some(cursor) if filter.get_block_hash().is_none() => {
+                let from = filter.get_from_block().unwrap_or_default().max(cursor.block_number.to());
+                filter.from_block(from)
+            }
+            _ => filter,
+        };
+        let range = match filter.get_block_hash() {
+            Some(_) => None,
+            None => match self.logs_block_range(&filter).await? {
+                Some(range) => Some(range),
+                None => return Ok(PaginatedLogs { logs: Vec::new(), cursor: None }),
+            },
+        };
+        let Some(database_filter) = self.logs_database_filter(&filter, range).await? else {
+            return Ok(PaginatedLogs { logs: Vec::new(), cursor: None });
+        };
+
+        // Log indexes are not stored padded in the database, they are ordered by their length first
+        let mut pipeline = vec![
+            doc! {"$match": database_filter},
+            doc! {"$addFields": {"logIndexLength": {"$strLenCP": "$log.logIndex"}}},
+        ];
+        if let Some(cursor) = cursor {
+            let block_number = format_hex(cursor.block_number, BLOCK_NUMBER_HEX_STRING_LEN);
+            let log_index = format!("{:#x}", cursor.log_index);
+            let log_index_length = i64::try_from(log_index.len()).unwrap_or(i64::MAX);
+            let same_block = doc! {"log.blockNumber": &block_number};
+            let mut longer_index = same_block.clone();
+            longer_index.insert("logIndexLength", doc! {"$gt": log_index_length});
+            let mut greater_index = same_block;
+            greater_index.extend(doc! {"logIndexLength": log_index_length, "log.logIndex": {"$gt": log_index}});
+            pipeline.push(doc! {"$match": {"$or": [
+                {"log.blockNumber": {"$gt": &block_number}},
+                longer_index,
+                greater_index,
+            ]}});
+        }
+        // One more log than the page size is fetched to know whether there is a next page
+        pipeline.push(doc! {"$sort": {"log.blockNumber": 1, "logIndexLength": 1, "log.logIndex": 1}});
+        pipeline.push(doc! {"$limit": i64::try_from(page_size).unwrap_or(i64::MAX).saturating_add(1)});
+
+        let mut logs: Vec<reth_rpc_types::Log> =
+            self.database.get_aggregate::<StoredLog>(pipeline).await?.into_iter().map(Into::into).collect();
+        let page_size = usize::try_from(page_size).unwrap_or(usize::MAX);
+        let has_next_page = logs.len() > page_size;
+        logs.truncate(page_size);
+        let cursor = if has_next_page { logs.last().and_then(LogCursor::from_log) } else { None };
+        dedup_pending_logs(&mut logs);
+
+        Ok(PaginatedLogs { logs, cursor })
     }
 
     async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>) -> EthProviderResult<Bytes> {
@@ -455,7 +621,11 @@ where
         // Increase the gas used by 20% to make sure the transaction will not fail due to gas.
         // This is a temporary solution until we have a proper gas estimation.
         // Does not apply to Hive feature otherwise end2end tests will fail.
-        let gas_used = if cfg!(feature = "hive") { gas_used } else { gas_used * 120 / 100 };
+        let gas_used = if cfg!(feature = "hive") {
+            gas_used
+        } else {
+            gas_used.checked_mul(120).ok_or(TransactionError::GasOverflow)? / 100
+        };
         Ok(U256::from(gas_used))
     }
 
@@ -469,27 +639,32 @@ where
             return Ok(FeeHistory::default());
         }
 
+        // The pending block has no number of its own and is treated as the block following the latest one,
+        // its partial gas usage is included in the history if the indexer stored it.
         let end_block = self.tag_into_block_number(newest_block).await?;
         let end_block = end_block.to::<u64>();
         let end_block_plus = end_block.saturating_add(1);
 
-        // 0 <= start_block <= end_block
-        let start_block = end_block_plus.saturating_sub(block_count.to());
+        // The block count is clamped so the history never walks past the earliest block:
+        // earliest_block_number <= start_block <= end_block
+        let block_count = block_count.to::<u64>().min(end_block_plus.saturating_sub(self.config.earliest_block_number));
+        let start_block = end_block_plus - block_count;
 
         // TODO: check if we should use a projection since we only need the gasLimit and gasUsed.
         // This means we need to introduce a new type for the StoredHeader.
         let header_filter = doc! {"$and": [ { "header.number": { "$gte": format_hex(start_block, BLOCK_NUMBER_HEX_STRING_LEN) } }, { "header.number": { "$lte": format_hex(end_block, BLOCK_NUMBER_HEX_STRING_LEN) } } ] };
-        let blocks: Vec<StoredHeader> = self.database.get(header_filter, None).await?;
+        let mut blocks: Vec<StoredHeader> = self.database.get(header_filter, None).await?;
 
         if blocks.is_empty() {
             return Err(EthApiError::UnknownBlock);
         }
+        blocks.sort_by_key(|header| header.header.number);
 
         let gas_used_ratio = blocks
             .iter()
             .map(|header| {
                 let gas_used = header.header.gas_used as f64;
-                let mut gas_limit = header.header.gas_limit as f64;
+                let mut gas_limit = self.config.block_gas_limit.unwrap_or(header.header.gas_limit) as f64;
                 if gas_limit == 0. {
                     gas_limit = 1.;
                 };
@@ -527,6 +702,11 @@ where
         // Recover the signer from the transaction
         let signer = transaction_signed.recover_signer().ok_or(SignatureError::RecoveryError)?;
 
+        // Only the allowlisted senders can send transactions, if an allowlist is set
+        if self.config.sender_allowlist.as_ref().is_some_and(|allowlist| !allowlist.contains(&signer)) {
+            return Err(EthApiError::Unauthorized(signer));
+        }
+
         // Get the chain id
         let maybe_chain_id = transaction_signed.chain_id();
 
@@ -554,9 +734,15 @@ where
             // TODO(Kakarot Fee Mechanism): When we no longer need to use the Starknet fees, remove this line.
             // We need to get the balance (in Kakarot/Starknet native Token) of the signer to compute the Starknet maximum `max_fee`.
             // We used to set max_fee = u64::MAX, but it'll fail if the signer doesn't have enough balance to pay the fees.
-            let eth_fees_per_gas =
-                transaction_signed.effective_gas_price(Some(transaction_signed.max_fee_per_gas() as u64)) as u64;
-            let eth_fees = eth_fees_per_gas.saturating_mul(transaction_signed.gas_limit());
+            let max_fee_per_gas = U256::from(transaction_signed.max_fee_per_gas());
+            let eth_fees_per_gas = effective_gas_price(
+                max_fee_per_gas,
+                max_fee_per_gas,
+                transaction_signed.max_priority_fee_per_gas().map(U256::from),
+            )?;
+            let eth_fees = transaction_fees(eth_fees_per_gas, U256::from(transaction_signed.gas_limit()))?;
+            let eth_fees: u64 =
+                eth_to_starknet_fee(eth_fees, self.config.starknet_fee_ratio)?.try_into().unwrap_or(u64::MAX);
             let balance = self.balance(signer, None).await?;
             let max_fee: u64 = balance.try_into().unwrap_or(u64::MAX);
             let max_fee = (u128::from(max_fee) * 80 / 100) as u64;
@@ -603,6 +789,17 @@ where
         let res =
             self.starknet_provider.add_invoke_transaction(starnet_transaction).await.map_err(KakarotError::from)?;
 
+        // Keep track of the Starknet hash of the transaction
+        let hashes = StoredStarknetTransactionHash {
+            eth_hash: transaction_signed.hash,
+            starknet_hash: B256::from_slice(&res.transaction_hash.to_bytes_be()),
+        };
+        let filter = into_filter("ethHash", &hashes.eth_hash, HASH_HEX_STRING_LEN);
+        // The transaction is already submitted, failing to store its hashes only loses the mapping
+        if let Err(err) = self.database.update_one(hashes, filter, true).await {
+            tracing::warn!("Failed to store the Starknet hash of transaction {}: {err}", transaction_signed.hash);
+        }
+
         // Return transaction hash if testing feature is enabled, otherwise log and return Ethereum hash
         if cfg!(feature = "testing") {
             return Ok(B256::from_slice(&res.transaction_hash.to_bytes_be()[..]));
@@ -614,9 +811,14 @@ where
     }
 
     async fn gas_price(&self) -> EthProviderResult<U256> {
-        let kakarot_contract = KakarotCoreReader::new(*KAKAROT_ADDRESS, &self.starknet_provider);
+        let kakarot_contract = KakarotCoreReader::new(self.kakarot_address, &self.starknet_provider);
         let gas_price = kakarot_contract.get_base_fee().call().await.map_err(KakarotError::from)?.base_fee;
-        Ok(into_via_wrapper!(gas_price))
+        let gas_price: U256 = into_via_wrapper!(gas_price);
+        Ok(gas_price.max(U256::from(self.config.min_gas_price)))
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> EthProviderResult<U256> {
+        Ok(U256::from(*MAX_PRIORITY_FEE_PER_GAS).max(U256::from(self.config.min_gas_price)))
     }
 
     async fn block_receipts(&self, block_id: Option<BlockId>) -> EthProviderResult<Option<Vec<TransactionReceipt>>> {
@@ -628,15 +830,16 @@ where
                 }
 
                 let filter = into_filter("receipt.blockNumber", &block_number, BLOCK_NUMBER_HEX_STRING_LEN);
-                let tx: Vec<StoredTransactionReceipt> = self.database.get(filter, None).await?;
-                Ok(Some(tx.into_iter().map(Into::into).collect()))
+                let receipts = self.receipts_with_gas_used(filter).await?;
+                Ok(Some(self.with_contract_addresses(receipts).await?))
             }
             BlockId::Hash(hash) => {
                 if !self.block_exists(hash.block_hash.into()).await? {
                     return Ok(None);
                 }
                 let filter = into_filter("receipt.blockHash", &hash.block_hash, HASH_HEX_STRING_LEN);
-                Ok(Some(self.database.get_and_map_to::<_, StoredTransactionReceipt>(filter, None).await?))
+                let receipts = self.receipts_with_gas_used(filter).await?;
+                Ok(Some(self.with_contract_addresses(receipts).await?))
             }
         }
     }
@@ -669,18 +872,247 @@ where
             content
         }))
     }
+
+    async fn starknet_block(&self, block_id: Option<BlockId>) -> EthProviderResult<MaybePendingBlockWithTxs> {
+        let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        Ok(self.starknet_provider.get_block_with_txs(starknet_block_id).await.map_err(KakarotError::from)?)
+    }
+
+    async fn starknet_transaction_hash(&self, hash: B256) -> EthProviderResult<Option<B256>> {
+        let filter = into_filter("ethHash", &hash, HASH_HEX_STRING_LEN);
+        let hashes = self.database.get_one::<StoredStarknetTransactionHash>(filter, None).await?;
+        Ok(hashes.map(|hashes| hashes.starknet_hash))
+    }
 }
 
 impl<SP> EthDataProvider<SP>
 where
     SP: starknet::providers::Provider + Send + Sync,
 {
-    pub async fn new(database: Database, starknet_provider: SP) -> Result<Self> {
+    pub async fn new(database: Database, starknet_provider: SP, config: KakarotConfig) -> Result<Self> {
+        config.validate()?;
         // We take the chain_id modulo u32::MAX to ensure compatibility with tooling
         // see: https://github.com/ethereum/EIPs/issues/2294
         // Note: Metamask is breaking for a chain_id = u64::MAX - 1
         let chain_id = (FieldElement::from(u32::MAX) & starknet_provider.chain_id().await?).try_into().unwrap(); // safe unwrap
-        Ok(Self { database, starknet_provider, chain_id })
+        Ok(Self {
+            database,
+            starknet_provider,
+            chain_id,
+            kakarot_address: *KAKAROT_ADDRESS,
+            block_cache: (config.block_cache_size > 0).then(|| Arc::new(BlockCache::new(config.block_cache_size))),
+            scan_misses: Arc::new(ScanMissCache::new(TRANSACTION_SCAN_MISSES)),
+            #[cfg(feature = "log-index")]
+            log_index: (config.log_index_blocks > 0).then(|| Arc::new(LogIndex::new(config.log_index_blocks))),
+            config,
+        })
+    }
+
+    /// Resolves the block range of the filter into block numbers, the pending block being the one
+    /// following the latest block. Returns `None` if the range starts after the head of the
+    /// chain, and an error if both bounds are set and inverted.
+    async fn logs_block_range(&self, filter: &Filter) -> EthProviderResult<Option<(u64, u64)>> {
+        let current_block: u64 = self.block_number().await?.try_into().map_err(|_| EthApiError::UnknownBlockNumber)?;
+
+        let from_block = filter.block_option.get_from_block().copied();
+        let to_block = filter.block_option.get_to_block().copied();
+        let from = match from_block {
+            Some(from_block) => self.tag_into_block_number(from_block).await?.to::<u64>(),
+            None => 0,
+        };
+        let to = match to_block {
+            Some(to_block) => self.tag_into_block_number(to_block).await?.to::<u64>(),
+            None => current_block,
+        };
+        let head = if [from_block, to_block].contains(&Some(BlockNumberOrTag::Pending)) {
+            current_block.saturating_add(1)
+        } else {
+            current_block
+        };
+
+        // As for Geth, a range missing its upper bound is empty rather than inverted
+        if from_block.is_some() && to_block.is_some() && to < from {
+            return Err(EthApiError::InvalidBlockRange);
+        }
+        Ok(match (from, to) {
+            (from, _) if from > head => None,
+            (from, to) => Some((from, to.min(head))),
+        })
+    }
+
+    /// Builds the database filter matching the logs of the given filter, within the block range
+    /// resolved by [`Self::logs_block_range`], which is `None` for a filter on a block hash.
+    /// Returns `None` if no log can match the filter.
+    async fn logs_database_filter(
+        &self,
+        filter: &Filter,
+        range: Option<(u64, u64)>,
+    ) -> EthProviderResult<Option<mongodb::bson::Document>> {
+        // Create the database filter.
+        let mut database_filter = if let Some(block_hash) = filter.get_block_hash() {
+            // We filter by block hash on matching the exact block hash.
+            doc! {
+                "log.blockHash": format_hex(block_hash, HASH_HEX_STRING_LEN)
+            }
+        } else {
+            let Some((from, to)) = range else {
+                return Ok(None);
+            };
+            // Reading the headers of a large range would cost more than the logs query
+            if self.config.logs_bloom_filter
+                && !is_unfiltered(filter)
+                && to.saturating_sub(from) < LOGS_BLOOM_FILTER_MAX_BLOCKS
+            {
+                // We only filter on the blocks whose bloom may contain the logs.
+                let blocks = self.logs_candidate_blocks(filter, from, to).await?;
+                if blocks.is_empty() {
+                    return Ok(None);
+                }
+                doc! {
+                    "log.blockNumber": {"$in": blocks.into_iter().map(|n| format_hex(n, BLOCK_NUMBER_HEX_STRING_LEN)).collect::<Vec<_>>()},
+                }
+            } else {
+                // We filter by block number using $gte and $lte.
+                doc! {
+                    "log.blockNumber": {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
+                }
+            }
+        };
+
+        // TODO: this will work for now but isn't very efficient. Would need to:
+        // 1. Create the bloom filter from the topics
+        // 2. Query the database for logs within block range with the bloom filter
+        // 3. Filter this reduced set of logs by the topics
+        // 4. Limit the number of logs returned
+
+        // Convert the topics to a MongoDB filter and add it to the database filter
+        let logs_filter = to_logs_filter(&filter.topics);
+        database_filter.extend(logs_filter);
+
+        // Add the address filter if any
+        if let Some(addresses) = filter.address.to_value_or_array().map(|a| match a {
+            ValueOrArray::Value(address) => vec![address],
+            ValueOrArray::Array(addresses) => addresses,
+        }) {
+            database_filter.insert(
+                "log.address",
+                doc! {"$in": addresses.into_iter().map(|a| format_hex(a, ADDRESS_HEX_STRING_LEN)).collect::<Vec<_>>()},
+            );
+        }
+
+        self.exclude_denied_log_topics(&mut database_filter);
+
+        Ok(Some(database_filter))
+    }
+
+    /// Excludes the logs with a denied first topic from the database filter.
+    fn exclude_denied_log_topics(&self, database_filter: &mut mongodb::bson::Document) {
+        if !self.config.denied_log_topics.is_empty() {
+            let denied_topics = self
+                .config
+                .denied_log_topics
+                .iter()
+                .map(|topic| format_hex(topic, LOGS_TOPICS_HEX_STRING_LEN))
+                .collect::<Vec<_>>();
+            database_filter.insert("$nor", vec![doc! {"log.topics.0": {"$in": denied_topics}}]);
+        }
+    }
+
+    /// Returns the numbers of the blocks of the range whose header bloom may contain logs
+    /// matching the filter. The pending block is always a candidate, as its bloom can still
+    /// change while transactions are added to it.
+    async fn logs_candidate_blocks(&self, filter: &Filter, from: u64, to: u64) -> EthProviderResult<Vec<u64>> {
+        let headers_filter = doc! {
+            "header.number": {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
+        };
+        let projection = doc! {"header.number": 1, "header.hash": 1, "header.logsBloom": 1};
+        let headers: Vec<StoredHeaderBloom> = self.database.get(headers_filter, projection).await?;
+
+        Ok(headers
+            .into_iter()
+            .filter(|header| {
+                header.header.hash.unwrap_or_default().is_zero()
+                    || bloom_matches_filter(header.header.logs_bloom, filter)
+            })
+            .map(|header| header.header.number.to())
+            .collect())
+    }
+
+    /// Returns the logs of the blocks `from..=to` matching the filter from the log index, or
+    /// `None` if the index is disabled or the range isn't within the most recent sealed blocks
+    /// it holds. The blocks missing from the index are indexed first, and the indexed blocks
+    /// whose hash changed in the database are indexed again.
+    #[cfg(feature = "log-index")]
+    async fn indexed_logs(
+        &self,
+        filter: &Filter,
+        from: u64,
+        to: u64,
+    ) -> EthProviderResult<Option<Vec<reth_rpc_types::Log>>> {
+        let Some(log_index) = &self.log_index else { return Ok(None) };
+
+        // The pending block is not indexed as its logs can still change
+        let head = self.block_number().await?.to::<u64>();
+        if to > head || from.saturating_add(log_index.max_blocks()) <= head {
+            return Ok(None);
+        }
+
+        let headers_filter = doc! {
+            "header.number": {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
+        };
+        let projection = doc! {"header.number": 1, "header.hash": 1, "header.logsBloom": 1};
+        let headers: Vec<_> = self
+            .database
+            .get::<StoredHeaderBloom>(headers_filter, projection)
+            .await?
+            .into_iter()
+            .filter_map(|header| Some((header.header.number.to::<u64>(), header.header.hash?)))
+            .filter(|(_, hash)| !hash.is_zero())
+            .collect();
+
+        let missing = log_index.sync(&headers);
+        if !missing.is_empty() {
+            let hashes = missing.iter().map(|(_, hash)| format_hex(hash, HASH_HEX_STRING_LEN)).collect::<Vec<_>>();
+            let mut database_filter = doc! {"log.blockHash": {"$in": hashes}};
+            self.exclude_denied_log_topics(&mut database_filter);
+            let logs = self.database.get_and_map_to::<_, StoredLog>(database_filter, None).await?;
+            log_index.insert(&missing, logs);
+        }
+
+        let Some(logs) = log_index.get(filter, from, to) else { return Ok(None) };
+        if let Some(max_logs) = self.config.max_logs {
+            if logs.len() as u64 > max_logs {
+                return Err(EthApiError::QueryLimitExceeded(max_logs));
+            }
+        }
+        Ok(Some(logs))
+    }
+
+    /// Checks that the Kakarot address hosts a contract with the expected class hash.
+    pub async fn check_kakarot_class_hash(&self, expected_class_hash: FieldElement) -> Result<()> {
+        let class_hash = self
+            .starknet_provider
+            .get_class_hash_at(starknet::core::types::BlockId::Tag(BlockTag::Latest), self.kakarot_address)
+            .await?;
+        if class_hash != expected_class_hash {
+            return Err(eyre::eyre!(
+                "Kakarot contract at {:#x} has class hash {:#x}, expected {:#x}",
+                self.kakarot_address,
+                class_hash,
+                expected_class_hash
+            ));
+        }
+        Ok(())
+    }
+
+    /// Warms up the connections to the database and to the Starknet provider by fetching the
+    /// latest block number and base fee, so the first requests don't pay for their setup. The
+    /// chain id is fetched when the provider is built and served from memory afterwards.
+    pub async fn warm_up(&self) -> Result<()> {
+        let block_number = self.block_number().await?;
+        let base_fee = self.gas_price().await?;
+        tracing::info!("Warmed up at block {block_number} with chain id {} and base fee {base_fee}", self.chain_id);
+        Ok(())
     }
 
     #[cfg(feature = "testing")]
@@ -694,6 +1126,13 @@ where
         request: TransactionRequest,
         block_id: Option<BlockId>,
     ) -> EthProviderResult<CallInput> {
+        // The legacy gas price can't be combined with the EIP-1559 fees, as in geth
+        if request.gas_price.is_some()
+            && (request.max_fee_per_gas.is_some() || request.max_priority_fee_per_gas.is_some())
+        {
+            return Err(TransactionError::ConflictingFeeFields.into());
+        }
+
         // unwrap option
         let to: kakarot_core::core::Option = {
             match request.to {
@@ -710,7 +1149,11 @@ where
         let data = request.input.into_input().unwrap_or_default();
         let calldata: Vec<FieldElement> = data.into_iter().map_into().collect();
 
-        let gas_limit = into_via_try_wrapper!(request.gas.unwrap_or(CALL_REQUEST_GAS_LIMIT))?;
+        let gas_limit = match request.gas {
+            Some(gas_limit) => gas_limit,
+            None => self.default_call_gas_limit(block_id).await?,
+        };
+        let gas_limit = into_via_try_wrapper!(gas_limit)?;
 
         // We cannot unwrap_or_default() here because Kakarot.eth_call will
         // Reject transactions with gas_price < Kakarot.base_fee
@@ -740,6 +1183,23 @@ where
         Ok(CallInput { nonce, from, to, gas_limit, gas_price, value, calldata })
     }
 
+    /// Returns the gas limit of a call without gas limit: the configured call gas limit if
+    /// any, otherwise the gas limit of the block the call is made against.
+    async fn default_call_gas_limit(&self, block_id: Option<BlockId>) -> EthProviderResult<u128> {
+        if let Some(gas_limit) = self.config.call_gas_limit.or(self.config.block_gas_limit) {
+            return Ok(gas_limit);
+        }
+
+        // The pending block might not be stored yet, use the latest block instead
+        let block_id = match block_id {
+            None | Some(BlockId::Number(BlockNumberOrTag::Pending)) => BlockId::Number(BlockNumberOrTag::Latest),
+            Some(block_id) => block_id,
+        };
+        let gas_limit = EthereumProvider::header(self, &block_id).await?.map(|header| header.gas_limit);
+
+        Ok(gas_limit.filter(|gas_limit| *gas_limit != 0).unwrap_or(CALL_REQUEST_GAS_LIMIT))
+    }
+
     /// Call the Kakarot contract with the given request.
     async fn call_helper(
         &self,
@@ -749,7 +1209,7 @@ where
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
         let call_input = self.prepare_call_input(request, block_id).await?;
 
-        let kakarot_contract = KakarotCoreReader::new(*KAKAROT_ADDRESS, &self.starknet_provider);
+        let kakarot_contract = KakarotCoreReader::new(self.kakarot_address, &self.starknet_provider);
         let call_output = kakarot_contract
             .eth_call(
                 &call_input.nonce,
@@ -784,7 +1244,7 @@ where
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
         let call_input = self.prepare_call_input(request, block_id).await?;
 
-        let kakarot_contract = KakarotCoreReader::new(*KAKAROT_ADDRESS, &self.starknet_provider);
+        let kakarot_contract = KakarotCoreReader::new(self.kakarot_address, &self.starknet_provider);
         let estimate_gas_output = kakarot_contract
             .eth_estimate_gas(
                 &call_input.nonce,
@@ -831,16 +1291,46 @@ where
             .map_err(|_| EthApiError::UnknownBlock)
     }
 
-    /// Return the transactions given a block id.
+    /// Prepares the timestamp of a header before serving it. The timestamp of the pending block
+    /// is clamped so that it is not lower than its parent's, and the sealed ones are checked.
+    async fn serve_timestamp(&self, header: &mut Header) -> EthProviderResult<()> {
+        if !header.hash.unwrap_or_default().is_zero() {
+            check_timestamp(header);
+            return Ok(());
+        }
+        let parent_number = header.number.unwrap_or_default().saturating_sub(1);
+        let parent_filter = into_filter("header.number", &parent_number, BLOCK_NUMBER_HEX_STRING_LEN);
+        if let Some(parent) = self.database.get_one::<StoredHeader>(parent_filter, None).await? {
+            clamp_pending_timestamp(header, &parent.header);
+        }
+        Ok(())
+    }
+
+    /// Return the transactions given a block id. The reverted transactions are included, as they
+    /// are mined like any other transaction, unless `exclude_reverted_transactions` is set.
     pub(crate) async fn transactions(
         &self,
         block_id: BlockHashOrNumber,
         full: bool,
     ) -> EthProviderResult<BlockTransactions> {
-        let transactions_filter = match block_id {
+        let mut transactions_filter = match block_id {
             BlockHashOrNumber::Hash(hash) => into_filter("tx.blockHash", &hash, HASH_HEX_STRING_LEN),
             BlockHashOrNumber::Number(number) => into_filter("tx.blockNumber", &number, BLOCK_NUMBER_HEX_STRING_LEN),
         };
+        if self.config.exclude_reverted_transactions {
+            let mut receipts_filter = match block_id {
+                BlockHashOrNumber::Hash(hash) => into_filter("receipt.blockHash", &hash, HASH_HEX_STRING_LEN),
+                BlockHashOrNumber::Number(number) => {
+                    into_filter("receipt.blockNumber", &number, BLOCK_NUMBER_HEX_STRING_LEN)
+                }
+            };
+            receipts_filter.insert("receipt.status", "0x0");
+            let reverted: Vec<TransactionReceipt> =
+                self.database.get_and_map_to::<_, StoredTransactionReceipt>(receipts_filter, None).await?;
+            let reverted_hashes: Vec<_> =
+                reverted.iter().map(|receipt| format_hex(receipt.transaction_hash, HASH_HEX_STRING_LEN)).collect();
+            transactions_filter.insert("tx.hash", doc! {"$nin": reverted_hashes});
+        }
         let block_transactions = if full {
             BlockTransactions::Full(
                 self.database.get_and_map_to::<_, StoredTransaction>(transactions_filter, None).await?,
@@ -856,37 +1346,245 @@ where
         Ok(block_transactions)
     }
 
+    /// Sets the contract address of the receipts missing it to the address of the first contract
+    /// deployed by their transaction, e.g. through CREATE2 by a factory, according to the deployment
+    /// events of their Starknet receipt. The deployment transactions whose Starknet receipt is
+    /// unknown fall back on the CREATE address of their sender and nonce.
+    async fn with_contract_addresses(
+        &self,
+        mut receipts: Vec<TransactionReceipt>,
+    ) -> EthProviderResult<Vec<TransactionReceipt>> {
+        let starknet_receipts = self.starknet_receipts(&receipts, |receipt| receipt.contract_address.is_none()).await?;
+        for (position, starknet_receipt) in starknet_receipts {
+            receipts[position].contract_address =
+                deployed_contract_address(invoke_events(&starknet_receipt), self.kakarot_address);
+        }
+
+        for receipt in receipts.iter_mut().filter(|receipt| receipt.contract_address.is_none() && receipt.to.is_none())
+        {
+            if let Some(transaction) = self.transaction_by_hash(receipt.transaction_hash).await? {
+                fill_contract_address(receipt, &transaction);
+            }
+        }
+        Ok(receipts)
+    }
+
+    /// Returns the receipts of a block matching the filter, with their gas used approximated if
+    /// missing and their cumulative gas used accumulated from it, the only way the cumulative gas
+    /// used of the receipts is computed.
+    async fn receipts_with_gas_used(&self, filter: Document) -> EthProviderResult<Vec<TransactionReceipt>> {
+        let receipts: Vec<TransactionReceipt> =
+            self.database.get_and_map_to::<_, StoredTransactionReceipt>(filter, None).await?;
+        let mut receipts = self.with_approximate_gas_used(receipts).await?;
+        accumulate_gas_used(&mut receipts);
+        Ok(receipts)
+    }
+
+    /// Approximates the gas used of the receipts missing it, e.g. when the gas of the execution
+    /// was not traced, from the actual fees of the Starknet transactions carrying them, whose
+    /// execution results also set the status of the receipts. Only the transactions sent through
+    /// the RPC are known to their Starknet hash.
+    async fn with_approximate_gas_used(
+        &self,
+        mut receipts: Vec<TransactionReceipt>,
+    ) -> EthProviderResult<Vec<TransactionReceipt>> {
+        let starknet_receipts = self.starknet_receipts(&receipts, |receipt| receipt.gas_used == 0).await?;
+        for (position, starknet_receipt) in starknet_receipts {
+            let receipt = &mut receipts[position];
+            set_status(receipt, execution_result_to_status(starknet_receipt.execution_result()));
+            if let Some(actual_fee) = invoke_actual_fee(&starknet_receipt) {
+                receipt.gas_used =
+                    approximate_gas_used(actual_fee, receipt.effective_gas_price, self.config.starknet_fee_ratio);
+                tracing::debug!(
+                    "Approximated the gas used by transaction {} to {} from its Starknet fee",
+                    receipt.transaction_hash,
+                    receipt.gas_used
+                );
+            }
+        }
+        Ok(receipts)
+    }
+
+    /// Returns the Starknet receipts of the receipts matching the predicate, with their positions
+    /// in the receipts. Only the transactions sent through the RPC are known to their Starknet
+    /// hash, the other ones, like the receipts which can't be fetched, are left out.
+    async fn starknet_receipts(
+        &self,
+        receipts: &[TransactionReceipt],
+        predicate: impl Fn(&TransactionReceipt) -> bool,
+    ) -> EthProviderResult<Vec<(usize, MaybePendingTransactionReceipt)>> {
+        let missing: Vec<_> = receipts
+            .iter()
+            .filter(|receipt| predicate(receipt))
+            .map(|receipt| format_hex(receipt.transaction_hash, HASH_HEX_STRING_LEN))
+            .collect();
+        if missing.is_empty() {
+            return Ok(Vec::new());
+        }
+        let hashes: Vec<StoredStarknetTransactionHash> =
+            self.database.get(doc! {"ethHash": {"$in": missing}}, None).await?;
+        let hashes: HashMap<_, _> = hashes.into_iter().map(|hashes| (hashes.eth_hash, hashes.starknet_hash)).collect();
+
+        let mut positions = Vec::with_capacity(hashes.len());
+        let mut starknet_hashes = Vec::with_capacity(hashes.len());
+        for (position, receipt) in receipts.iter().enumerate().filter(|(_, receipt)| predicate(receipt)) {
+            if let Some(starknet_hash) = hashes.get(&receipt.transaction_hash) {
+                positions.push(position);
+                starknet_hashes.push(
+                    FieldElement::from_bytes_be(&starknet_hash.0)
+                        .map_err(|_| EthApiError::from(EthereumDataFormatError::FeltOverflow))?,
+                );
+            }
+        }
+
+        let starknet_receipts = self.fetch_receipts(starknet_hashes).await;
+        Ok(positions
+            .into_iter()
+            .zip(starknet_receipts)
+            .filter_map(|(position, starknet_receipt)| Some((position, starknet_receipt?)))
+            .collect())
+    }
+
+    /// Fetches the Starknet receipts of the given Starknet transactions, in the same order.
+    /// The Starknet provider has no batch receipt method, so the receipts are fetched with
+    /// concurrent single requests. A receipt which can't be fetched is `None`.
+    async fn fetch_receipts(&self, hashes: Vec<FieldElement>) -> Vec<Option<MaybePendingTransactionReceipt>> {
+        futures::stream::iter(hashes)
+            .map(|hash| async move {
+                self.starknet_provider
+                    .get_transaction_receipt(hash)
+                    .await
+                    .map_err(|err| tracing::warn!("Failed to fetch the Starknet receipt of {hash:#x}: {err}"))
+                    .ok()
+            })
+            .buffered(BATCH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
     /// Get a block from the database based on a block hash or number.
     /// If full is true, the block will contain the full transactions, otherwise just the hashes
     async fn block(&self, block_id: BlockHashOrNumber, full: bool) -> EthProviderResult<Option<RichBlock>> {
-        let header = match self.header(block_id).await? {
+        let mut header = match self.header(block_id).await? {
             Some(h) => h.header,
             None => return Ok(None),
         };
+        self.serve_timestamp(&mut header).await?;
+        // The pending block is stored with a zero hash
+        let sealed_hash = header.hash.filter(|hash| !hash.is_zero());
+        if sealed_hash.is_none() {
+            header.hash = self.config.pending_block_hash.hash(&header);
+            // As per the spec, the number and nonce of a pending block are null, like its hash,
+            // unless a placeholder hash is returned to clients which can't handle these nulls
+            if header.hash.is_none() {
+                header.number = None;
+                header.nonce = None;
+            }
+        }
+        if let Some(coinbase) = self.config.coinbase {
+            header.miner = coinbase;
+        }
+        if let Some(gas_limit) = self.config.block_gas_limit {
+            header.gas_limit = gas_limit;
+        }
+        // The parent of the earliest block is not served, even if the Starknet block has a parent
+        if header.number == Some(self.config.earliest_block_number) {
+            header.parent_hash = B256::ZERO;
+        }
+
+        // Only sealed blocks are cached, the pending block can still change
+        let cache = self.block_cache.as_ref().zip(sealed_hash);
+        if let Some(block) = cache.and_then(|(cache, hash)| cache.get(hash, full)) {
+            return Ok(Some(block));
+        }
+
+        let block = rpc_block(header, self.transactions(block_id, full).await?)?;
+        if let Some((cache, hash)) = cache {
+            cache.insert(hash, full, block.clone());
+        }
+        Ok(Some(block))
+    }
+
+    /// Returns the Ethereum hash of the transaction carried by the given Starknet transaction.
+    /// The hash is looked up among the transactions sent through the RPC, and otherwise
+    /// recovered from the Starknet transaction itself.
+    pub async fn eth_hash_for_starknet(&self, starknet_hash: FieldElement) -> EthProviderResult<Option<B256>> {
+        let filter = into_filter("starknetHash", &B256::from_slice(&starknet_hash.to_bytes_be()), HASH_HEX_STRING_LEN);
+        if let Some(hashes) = self.database.get_one::<StoredStarknetTransactionHash>(filter, None).await? {
+            return Ok(Some(hashes.eth_hash));
+        }
+
+        let transaction = match self.starknet_provider.get_transaction_by_hash(starknet_hash).await {
+            Ok(transaction) => transaction,
+            Err(starknet::providers::ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {
+                return Ok(None)
+            }
+            Err(err) => return Err(KakarotError::from(err).into()),
+        };
+        if !is_kakarot_transaction(&transaction, self.kakarot_address) {
+            return Ok(None);
+        }
+        Ok(Some(starknet_to_rpc_transaction(&transaction, self.config.strict_signature_checks)?.hash))
+    }
+
+    /// Looks the transaction up in the most recent sealed Starknet blocks, for transactions
+    /// which are not indexed in the database yet. The blocks are fetched concurrently, those
+    /// failing to be fetched being skipped. A hash which is not found is remembered with the
+    /// latest block scanned, so that a later lookup only scans the blocks produced since.
+    async fn scan_transaction_by_hash(&self, hash: B256) -> EthProviderResult<Option<reth_rpc_types::Transaction>> {
+        if self.config.transaction_scan_depth == 0 {
+            return Ok(None);
+        }
 
-        // The withdrawals are not supported, hence the withdrawals_root should always be empty.
-        if let Some(withdrawals_root) = header.withdrawals_root {
-            if withdrawals_root != EMPTY_ROOT_HASH {
-                return Err(EthApiError::Unsupported("withdrawals"));
+        let latest = self.starknet_provider.block_number().await.map_err(KakarotError::from)?;
+        let mut oldest = latest.saturating_sub(self.config.transaction_scan_depth - 1);
+        if let Some(scanned) = self.scan_misses.get(hash) {
+            if scanned >= latest {
+                return Ok(None);
             }
+            oldest = oldest.max(scanned + 1);
         }
 
-        // This is how reth computes the block size.
-        // `https://github.com/paradigmxyz/reth/blob/v0.2.0-beta.5/crates/rpc/rpc-types-compat/src/block.rs#L66`
-        let size = reth_primitives::Header::try_from(header.clone())
-            .map_err(|_| EthereumDataFormatError::PrimitiveError)?
-            .length();
-        Ok(Some(
-            Block {
-                header,
-                transactions: self.transactions(block_id, full).await?,
-                uncles: Default::default(),
-                size: Some(U256::from(size)),
-                withdrawals: Some(Default::default()),
-                other: Default::default(),
+        let mut blocks = futures::stream::iter((oldest..=latest).rev())
+            .map(|number| async move {
+                let block_id = starknet::core::types::BlockId::Number(number);
+                (number, self.starknet_provider.get_block_with_txs(block_id).await)
+            })
+            .buffered(BATCH_CONCURRENCY);
+        let mut complete = true;
+        while let Some((number, block)) = blocks.next().await {
+            let block = match block {
+                Ok(MaybePendingBlockWithTxs::Block(block)) => block,
+                Ok(MaybePendingBlockWithTxs::PendingBlock(_)) => continue,
+                Err(err) => {
+                    tracing::warn!("Failed to fetch Starknet block {number} while scanning for {hash}: {err}");
+                    complete = false;
+                    continue;
+                }
+            };
+
+            // The index of the transaction is its position among the EVM transactions of the block
+            let transactions = filter_starknet_into_eth_txs(
+                &block.transactions,
+                self.kakarot_address,
+                self.config.strict_signature_checks,
+                self.config.include_l1_handlers,
+            );
+            if let Some((index, mut rpc_transaction)) =
+                transactions.into_iter().enumerate().find(|(_, transaction)| transaction.hash == hash)
+            {
+                rpc_transaction.block_hash = Some(B256::from_slice(&block.block_hash.to_bytes_be()));
+                rpc_transaction.block_number = Some(number);
+                rpc_transaction.transaction_index = u64::try_from(index).ok();
+                return Ok(Some(rpc_transaction));
             }
-            .into(),
-        ))
+        }
+
+        // A block which failed to be fetched may hold the transaction
+        if complete {
+            self.scan_misses.insert(hash, latest);
+        }
+        Ok(None)
     }
 
     /// Convert the given block id into a Starknet block id
@@ -924,8 +1622,8 @@ where
     /// Converts the given [`BlockNumberOrTag`] into a block number.
     async fn tag_into_block_number(&self, tag: BlockNumberOrTag) -> EthProviderResult<U64> {
         match tag {
-            // Converts the tag representing the earliest block into block number 0.
-            BlockNumberOrTag::Earliest => Ok(U64::ZERO),
+            // Converts the tag representing the earliest block into the configured earliest block number.
+            BlockNumberOrTag::Earliest => Ok(U64::from(self.config.earliest_block_number)),
             // Converts the tag containing a specific block number into a `U64`.
             BlockNumberOrTag::Number(number) => Ok(U64::from(number)),
             // Returns `self.block_number()` which is the block number of the latest finalized block.
@@ -948,7 +1646,6 @@ where
     async fn deploy_evm_transaction_signer(&self, signer: Address) -> EthProviderResult<()> {
         use crate::eth_provider::constant::{DEPLOY_WALLET, DEPLOY_WALLET_NONCE};
         use starknet::accounts::{Call, Execution};
-        use starknet::core::types::BlockTag;
         use starknet::core::utils::get_selector_from_name;
 
         let signer_starknet_address = starknet_address(signer);
@@ -962,7 +1659,7 @@ where
         if contract_not_found(&maybe_is_initialized) {
             let execution = Execution::new(
                 vec![Call {
-                    to: *KAKAROT_ADDRESS,
+                    to: self.kakarot_address,
                     selector: get_selector_from_name("deploy_externally_owned_account").unwrap(),
                     calldata: vec![into_via_wrapper!(signer)],
                 }],
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use reth_primitives::B256;
+
+/// In-memory cache of the transaction hashes which were not found by a scan of the most
+/// recent Starknet blocks, along with the latest block scanned. A later lookup of the same
+/// hash only scans the blocks produced since. The cache holds at most `capacity` hashes,
+/// the hashes scanned the longest ago being evicted first.
+#[derive(Debug)]
+pub struct ScanMissCache {
+    capacity: usize,
+    misses: Mutex<HashMap<B256, u64>>,
+}
+
+impl ScanMissCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, misses: Mutex::default() }
+    }
+
+    /// Returns the latest block scanned without finding the transaction with the given hash.
+    pub fn get(&self, hash: B256) -> Option<u64> {
+        self.misses.lock().ok()?.get(&hash).copied()
+    }
+
+    /// Records that the blocks up to `latest` don't hold the transaction with the given hash.
+    pub fn insert(&self, hash: B256, latest: u64) {
+        let Ok(mut misses) = self.misses.lock() else { return };
+        misses.insert(hash, latest);
+        while misses.len() > self.capacity {
+            let Some(oldest) = misses.iter().min_by_key(|(_, latest)| **latest).map(|(hash, _)| *hash) else {
+                return;
+            };
+            misses.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_miss_cache_evicts_oldest_scans() {
+        // Given
+        let cache = ScanMissCache::new(2);
+        let (first, second, third) = (B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+        cache.insert(first, 10);
+        cache.insert(second, 5);
+
+        // When
+        cache.insert(third, 12);
+
+        // Then
+        assert_eq!(cache.get(first), Some(10));
+        assert_eq!(cache.get(second), None);
+        assert_eq!(cache.get(third), Some(12));
+    }
+}
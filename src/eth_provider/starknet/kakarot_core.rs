@@ -64,6 +64,9 @@ lazy_static! {
     // Contract selectors
     pub static ref ETH_SEND_TRANSACTION: FieldElement = selector!("eth_send_transaction");
 
+    // Event selectors
+    pub static ref EVM_CONTRACT_DEPLOYED: FieldElement = selector!("evm_contract_deployed");
+
     // Maximum number of felts (bytes) in calldata
     pub static ref MAX_FELTS_IN_CALLDATA: usize = usize::from_str(
         &std::env::var("MAX_FELTS_IN_CALLDATA")
@@ -1,10 +1,16 @@
+pub mod keccak;
+
+use std::collections::HashSet;
 use std::fmt::LowerHex;
 
-use super::constant::LOGS_TOPICS_HEX_STRING_LEN;
+use super::constant::{LOGS_TOPICS_HEX_STRING_LEN, MAX_TIMESTAMP_DRIFT};
+use super::error::EthereumDataFormatError;
+use alloy_sol_types::{abi::TokenSeq, SolType};
 use cainome::cairo_serde::Error;
+use keccak::create_address;
 use mongodb::bson::{doc, Document};
-use reth_primitives::{U128, U256};
-use reth_rpc_types::{Topic, ValueOrArray};
+use reth_primitives::{Bloom, U128, U256};
+use reth_rpc_types::{Filter, FilteredParams, Header, Log, ReceiptEnvelope, Topic, TransactionReceipt, ValueOrArray};
 use starknet::{
     core::types::{ContractErrorData, StarknetError},
     providers::ProviderError,
@@ -71,6 +77,138 @@ pub fn split_u256<T: From<u128>>(value: impl Into<U256>) -> [T; 2] {
     [T::from(low), T::from(high)]
 }
 
+/// Joins the low and high 128 bits limbs of a U256 value, the inverse of [`split_u256`].
+/// Kakarot stores each 256 bits EVM storage word as two felts, the low limb at the storage
+/// key of the slot and the high limb at the following key.
+#[inline]
+pub fn join_u256(low: impl Into<U256>, high: impl Into<U256>) -> U256 {
+    low.into() | (high.into() << 128)
+}
+
+/// Returns true if the filter matches the logs of any address and topics.
+#[inline]
+pub(crate) fn is_unfiltered(filter: &Filter) -> bool {
+    filter.address.is_empty() && filter.topics.iter().all(Topic::is_empty)
+}
+
+/// Returns true if the bloom may contain logs matching the address and topics of the filter.
+/// A bloom can match one of the filter's values by chance but never misses one it contains.
+#[inline]
+pub(crate) fn bloom_matches_filter(bloom: Bloom, filter: &Filter) -> bool {
+    FilteredParams::matches_address(bloom, &FilteredParams::address_filter(&filter.address))
+        && FilteredParams::matches_topics(bloom, &FilteredParams::topics_filter(&filter.topics))
+}
+
+/// Removes the logs of the pending block whose transaction is also returned in a sealed block,
+/// the transaction having been mined since it was indexed as pending. Pending logs are stored
+/// with a zero block hash.
+pub(crate) fn dedup_pending_logs(logs: &mut Vec<Log>) {
+    let is_pending = |log: &Log| log.block_hash.map_or(true, |hash| hash.is_zero());
+    let sealed: HashSet<_> =
+        logs.iter().filter(|log| !is_pending(log)).filter_map(|log| log.transaction_hash).collect();
+    if sealed.is_empty() {
+        return;
+    }
+    logs.retain(|log| !is_pending(log) || log.transaction_hash.map_or(true, |hash| !sealed.contains(&hash)));
+}
+
+/// Clamps the timestamp of a pending block so that it is at least the timestamp
+/// of its parent, keeping the block timestamps monotonic.
+#[inline]
+pub(crate) fn clamp_pending_timestamp(pending: &mut Header, parent: &Header) {
+    if pending.timestamp < parent.timestamp {
+        tracing::debug!(
+            "clamping pending block timestamp {} to parent timestamp {}",
+            pending.timestamp,
+            parent.timestamp
+        );
+        pending.timestamp = parent.timestamp;
+    }
+}
+
+/// Logs a warning if the timestamp of the block isn't within a sane range, as returned by
+/// [`timestamp_issue`]. The timestamp is never modified.
+pub(crate) fn check_timestamp(header: &Header) {
+    if let Some(issue) = timestamp_issue(header) {
+        tracing::warn!("block {} has a timestamp {} {issue}", header.number.unwrap_or_default(), header.timestamp);
+    }
+}
+
+/// Returns why the timestamp of the block isn't within a sane range, if it isn't: it must not
+/// be zero for a non-genesis block nor further in the future than `MAX_TIMESTAMP_DRIFT`.
+///
+/// Block timestamps are the number of seconds since the Unix epoch in UTC, as for both
+/// Starknet and Ethereum. A timestamp which would only be sane once divided by 1000 was
+/// most likely stored in milliseconds, and is reported as such.
+pub(crate) fn timestamp_issue(header: &Header) -> Option<&'static str> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let max_timestamp = now.saturating_add(MAX_TIMESTAMP_DRIFT);
+
+    if header.timestamp == 0 && header.number.unwrap_or_default() != 0 {
+        return Some("which is zero");
+    }
+    if looks_like_milliseconds(header.timestamp, max_timestamp) {
+        return Some("which looks like milliseconds");
+    }
+    if header.timestamp > max_timestamp || i64::try_from(header.timestamp).is_err() {
+        return Some("far in the future");
+    }
+    None
+}
+
+/// Returns true if the timestamp is after `max_timestamp` in seconds but not in milliseconds.
+#[inline]
+const fn looks_like_milliseconds(timestamp: u64, max_timestamp: u64) -> bool {
+    timestamp > max_timestamp && timestamp / 1000 <= max_timestamp
+}
+
+/// Sets the contract address of the receipt of a deployment transaction if it is missing, to
+/// its CREATE address derived from the sender and the nonce of the transaction. Used when the
+/// deployment event of the transaction is unknown, the CREATE2 addresses requiring the event.
+pub(crate) fn fill_contract_address(receipt: &mut TransactionReceipt, transaction: &reth_rpc_types::Transaction) {
+    if receipt.contract_address.is_none() && transaction.to.is_none() {
+        receipt.contract_address = Some(create_address(transaction.from, transaction.nonce));
+    }
+}
+
+/// Orders the receipts of a block by transaction index and sets their cumulative gas used
+/// to the running sum of the gas used by the transactions up to and including theirs.
+pub(crate) fn accumulate_gas_used(receipts: &mut [TransactionReceipt]) {
+    receipts.sort_by_key(|receipt| receipt.transaction_index);
+    let mut cumulative_gas_used = 0u128;
+    for receipt in &mut *receipts {
+        cumulative_gas_used = cumulative_gas_used.saturating_add(receipt.gas_used);
+        set_cumulative_gas_used(receipt, cumulative_gas_used);
+    }
+}
+
+/// Sets the cumulative gas used of the receipt, whatever its transaction type.
+fn set_cumulative_gas_used(receipt: &mut TransactionReceipt, cumulative_gas_used: u128) {
+    match &mut receipt.inner {
+        ReceiptEnvelope::Legacy(inner)
+        | ReceiptEnvelope::Eip2930(inner)
+        | ReceiptEnvelope::Eip1559(inner)
+        | ReceiptEnvelope::Eip4844(inner) => inner.receipt.cumulative_gas_used = cumulative_gas_used,
+        #[allow(unreachable_patterns)]
+        _ => tracing::warn!("Unknown receipt type of transaction {}", receipt.transaction_hash),
+    }
+}
+
+/// Sets the status of the receipt, whatever its transaction type.
+pub(crate) fn set_status(receipt: &mut TransactionReceipt, status: bool) {
+    match &mut receipt.inner {
+        ReceiptEnvelope::Legacy(inner)
+        | ReceiptEnvelope::Eip2930(inner)
+        | ReceiptEnvelope::Eip1559(inner)
+        | ReceiptEnvelope::Eip4844(inner) => inner.receipt.status = status,
+        #[allow(unreachable_patterns)]
+        _ => tracing::warn!("Unknown receipt type of transaction {}", receipt.transaction_hash),
+    }
+}
+
 /// Checks if the error is a contract not found error.
 /// Some providers return a contract not found error when the contract is not deployed.
 /// Katana returns a contract error with a revert message containing "is not deployed".
@@ -104,13 +242,28 @@ pub(crate) fn entrypoint_not_found<T>(err: &Result<T, Error>) -> bool {
     }
 }
 
+/// Decodes the ABI-encoded return data of a call into the values of the Solidity types `T`,
+/// given as a tuple like the `returns` clause of a function, e.g. `(Uint<256>, Address)`.
+pub fn decode_call_result<T>(bytes: &[u8]) -> Result<T::RustType, EthereumDataFormatError>
+where
+    T: SolType,
+    for<'de> T::Token<'de>: TokenSeq<'de>,
+{
+    T::abi_decode_params(bytes, true).map_err(|_| EthereumDataFormatError::AbiDecodingError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::eth_provider::database::types::receipt::StoredTransactionReceipt;
+    use alloy_primitives::BloomInput;
+    use alloy_sol_types::sol_data;
+    use arbitrary::Arbitrary;
     use proptest::prelude::*;
-    use reth_primitives::B256;
+    use reth_primitives::{Address, B256};
     use reth_rpc_types::FilterSet;
     use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_into_filter_with_padding() {
@@ -144,6 +297,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_join_u256() {
+        proptest!(|(value in any::<U256>())| {
+            // When
+            let [low, high] = split_u256::<u128>(value);
+
+            // Then
+            assert_eq!(join_u256(low, high), value);
+        });
+    }
+
+    #[test]
+    fn test_join_u256_both_limbs() {
+        // Given
+        let value = U256::from_str("0x0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20").unwrap();
+
+        // When
+        let [low, high] = split_u256::<u128>(value);
+
+        // Then
+        assert_eq!(low, 0x1112_1314_1516_1718_191a_1b1c_1d1e_1f20);
+        assert_eq!(high, 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+        assert_eq!(join_u256(low, high), value);
+    }
+
     #[test]
     fn test_log_filter_empty() {
         // Given
@@ -194,4 +372,230 @@ mod tests {
                     == doc! { "log.topics.3": {"$in": ["0x0000000000000000000000000000000000000000000000000000000000000006", "0x0000000000000000000000000000000000000000000000000000000000000005"]} }
         );
     }
+
+    #[test]
+    fn test_clamp_pending_timestamp() {
+        // Given
+        let parent = Header { number: Some(10), timestamp: 1_000, ..Default::default() };
+        let mut pending = Header { number: Some(11), timestamp: 999, ..Default::default() };
+
+        // When
+        clamp_pending_timestamp(&mut pending, &parent);
+
+        // Then
+        assert_eq!(pending.timestamp, parent.timestamp);
+
+        // A timestamp ahead of the parent is kept
+        let mut pending = Header { number: Some(11), timestamp: 1_001, ..Default::default() };
+        clamp_pending_timestamp(&mut pending, &parent);
+        assert_eq!(pending.timestamp, 1_001);
+    }
+
+    #[test]
+    fn test_timestamp_issue() {
+        // Zero timestamp on a non-genesis block
+        assert!(timestamp_issue(&Header { number: Some(1), timestamp: 0, ..Default::default() }).is_some());
+        // Zero timestamp on the genesis block
+        assert!(timestamp_issue(&Header { number: Some(0), timestamp: 0, ..Default::default() }).is_none());
+        // Far future timestamp
+        assert!(timestamp_issue(&Header { number: Some(1), timestamp: u64::MAX, ..Default::default() }).is_some());
+        // Sane timestamp
+        assert!(timestamp_issue(&Header { number: Some(1), timestamp: 1_700_000_000, ..Default::default() }).is_none());
+    }
+
+    #[test]
+    fn test_timestamp_issue_milliseconds() {
+        // Given
+        let seconds = Header { number: Some(1), timestamp: 1_700_000_000, ..Default::default() };
+        let milliseconds = Header { timestamp: seconds.timestamp * 1000, ..seconds.clone() };
+
+        // When
+        let seconds_issue = timestamp_issue(&seconds);
+        let milliseconds_issue = timestamp_issue(&milliseconds);
+
+        // Then
+        assert_eq!(seconds_issue, None);
+        assert_eq!(milliseconds_issue, Some("which looks like milliseconds"));
+        let max_timestamp = seconds.timestamp + MAX_TIMESTAMP_DRIFT;
+        assert!(looks_like_milliseconds(milliseconds.timestamp, max_timestamp));
+        assert!(!looks_like_milliseconds(seconds.timestamp, max_timestamp));
+        assert!(!looks_like_milliseconds(u64::MAX, max_timestamp));
+    }
+
+    /// A writer collecting the formatted logs.
+    #[derive(Clone, Default)]
+    struct Logs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_check_timestamp() {
+        // Given
+        let logs = Logs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .with_max_level(tracing::Level::WARN)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let sane = Header { number: Some(1), timestamp: 1_700_000_000, ..Default::default() };
+        let milliseconds = Header { number: Some(2), timestamp: sane.timestamp * 1000, ..Default::default() };
+
+        // When
+        check_timestamp(&sane);
+        let sane_logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        check_timestamp(&milliseconds);
+        let milliseconds_logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+
+        // Then
+        assert!(sane_logs.is_empty());
+        assert!(milliseconds_logs.contains("WARN"));
+        assert!(milliseconds_logs.contains("block 2 has a timestamp 1700000000000 which looks like milliseconds"));
+    }
+
+    #[test]
+    fn test_dedup_pending_logs() {
+        // Given
+        let log = |block_hash: B256, transaction_hash: B256| Log {
+            block_hash: Some(block_hash),
+            transaction_hash: Some(transaction_hash),
+            ..Default::default()
+        };
+        let (mined, pending) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        let mut logs = vec![
+            log(B256::repeat_byte(9), mined),
+            log(B256::ZERO, mined),
+            log(B256::ZERO, pending),
+            log(B256::ZERO, pending),
+        ];
+
+        // When
+        dedup_pending_logs(&mut logs);
+
+        // Then
+        assert_eq!(logs, vec![log(B256::repeat_byte(9), mined), log(B256::ZERO, pending), log(B256::ZERO, pending)]);
+    }
+
+    #[test]
+    fn test_bloom_matches_filter() {
+        // Given
+        let address = Address::repeat_byte(1);
+        let topic = B256::repeat_byte(2);
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+        bloom.accrue(BloomInput::Raw(topic.as_slice()));
+
+        // When
+        let matching = Filter::new().address(address).event_signature(topic);
+        let other_topic = Filter::new().address(address).event_signature(B256::repeat_byte(3));
+        let other_address = Filter::new().address(Address::repeat_byte(3)).event_signature(topic);
+
+        // Then
+        assert!(bloom_matches_filter(bloom, &matching));
+        assert!(!bloom_matches_filter(bloom, &other_topic));
+        assert!(!bloom_matches_filter(bloom, &other_address));
+        assert!(bloom_matches_filter(Bloom::default(), &Filter::new()));
+        assert!(is_unfiltered(&Filter::new()));
+        assert!(!is_unfiltered(&matching));
+    }
+
+    #[test]
+    fn test_fill_contract_address() {
+        // Given
+        // Anvil first account
+        let from = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let factory = Address::from_str("0x4e59b44847b379578588920cA78FbF26c0B4956C").unwrap();
+        let deployment = reth_rpc_types::Transaction { from, nonce: 0, to: None, ..Default::default() };
+        let call = reth_rpc_types::Transaction { from, nonce: 1, to: Some(factory), ..Default::default() };
+        let receipt = StoredTransactionReceipt::arbitrary(&mut arbitrary::Unstructured::new(&[0u8; 1024])).unwrap();
+        let mut deployment_receipt =
+            TransactionReceipt { from, to: None, contract_address: None, ..receipt.receipt.clone() };
+        let mut call_receipt =
+            TransactionReceipt { from, to: Some(factory), contract_address: None, ..receipt.receipt };
+
+        // When
+        fill_contract_address(&mut deployment_receipt, &deployment);
+        fill_contract_address(&mut call_receipt, &call);
+
+        // Then
+        let expected = Address::from_str("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap();
+        assert_eq!(deployment_receipt.contract_address, Some(expected));
+        assert_eq!(call_receipt.contract_address, None);
+    }
+
+    #[test]
+    fn test_accumulate_gas_used() {
+        // Given
+        let receipt = StoredTransactionReceipt::arbitrary(&mut arbitrary::Unstructured::new(&[0u8; 1024])).unwrap();
+        let mut receipts = [(2, 30_000), (0, 21_000), (1, 50_000)]
+            .map(|(index, gas_used)| TransactionReceipt {
+                transaction_index: Some(index),
+                gas_used,
+                ..receipt.receipt.clone()
+            })
+            .to_vec();
+
+        // When
+        accumulate_gas_used(&mut receipts);
+
+        // Then
+        let cumulative_gas_used =
+            receipts.iter().map(|receipt| receipt.inner.cumulative_gas_used()).collect::<Vec<_>>();
+        assert_eq!(cumulative_gas_used, vec![21_000, 71_000, 101_000]);
+        assert!(cumulative_gas_used.windows(2).all(|window| window[0] < window[1]));
+        assert_eq!(cumulative_gas_used[2], receipts.iter().map(|receipt| receipt.gas_used).sum::<u128>());
+    }
+
+    #[test]
+    fn test_set_status() {
+        // Given
+        let receipt = StoredTransactionReceipt::arbitrary(&mut arbitrary::Unstructured::new(&[0u8; 1024])).unwrap();
+        let mut reverted = receipt.receipt.clone();
+        let mut succeeded = receipt.receipt;
+
+        // When
+        set_status(&mut reverted, false);
+        set_status(&mut succeeded, true);
+
+        // Then
+        assert!(!reverted.inner.as_receipt_with_bloom().unwrap().receipt.status);
+        assert!(succeeded.inner.as_receipt_with_bloom().unwrap().receipt.status);
+    }
+
+    #[test]
+    fn test_decode_call_result_uint256() {
+        // Given
+        let bytes = U256::from(0x1234).to_be_bytes::<32>();
+
+        // When
+        let (value,) = decode_call_result::<(sol_data::Uint<256>,)>(&bytes).unwrap();
+
+        // Then
+        assert_eq!(value, U256::from(0x1234));
+    }
+
+    #[test]
+    fn test_decode_call_result_tuple() {
+        // Given
+        let address = Address::repeat_byte(0xab);
+        let bytes = <(sol_data::Uint<256>, sol_data::Address)>::abi_encode_params(&(U256::MAX, address));
+
+        // When
+        let decoded = decode_call_result::<(sol_data::Uint<256>, sol_data::Address)>(&bytes).unwrap();
+        let truncated = decode_call_result::<(sol_data::Uint<256>, sol_data::Address)>(&bytes[..32]);
+
+        // Then
+        assert_eq!(decoded, (U256::MAX, address));
+        assert!(matches!(truncated, Err(EthereumDataFormatError::AbiDecodingError)));
+    }
 }
@@ -0,0 +1,60 @@
+use alloy_rlp::Encodable;
+pub use reth_primitives::keccak256;
+use reth_primitives::{Address, B256};
+
+/// Returns the selector of a function, the first 4 bytes of the Keccak-256 hash of its
+/// signature, e.g. `transfer(address,uint256)`.
+pub fn function_selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Returns the first topic of the logs of an event, the Keccak-256 hash of its signature,
+/// e.g. `Transfer(address,address,uint256)`.
+pub fn event_topic(signature: &str) -> B256 {
+    keccak256(signature)
+}
+
+/// Returns the address of the contract deployed with CREATE by the sender at the nonce, the last
+/// 20 bytes of the Keccak-256 hash of the RLP encoding of the list of the sender and the nonce.
+pub fn create_address(sender: Address, nonce: u64) -> Address {
+    let mut out = Vec::with_capacity(32);
+    alloy_rlp::Header { list: true, payload_length: sender.length() + nonce.length() }.encode(&mut out);
+    sender.encode(&mut out);
+    nonce.encode(&mut out);
+    Address::from_word(keccak256(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_function_selector() {
+        assert_eq!(function_selector("transfer(address,uint256)"), hex!("a9059cbb"));
+        assert_eq!(function_selector("balanceOf(address)"), hex!("70a08231"));
+        assert_eq!(function_selector("Error(string)"), hex!("08c379a0"));
+    }
+
+    #[test]
+    fn test_create_address() {
+        // Anvil first account, whose first deployments are the default addresses of Foundry
+        let sender = Address::from(hex!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert_eq!(create_address(sender, 0), Address::from(hex!("5FbDB2315678afecb367f032d93F642f64180aa3")));
+        assert_eq!(create_address(sender, 1), Address::from(hex!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512")));
+        assert_eq!(create_address(sender, 1), sender.create(1));
+    }
+
+    #[test]
+    fn test_event_topic() {
+        assert_eq!(
+            event_topic("Transfer(address,address,uint256)"),
+            B256::from(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"))
+        );
+        assert_eq!(
+            event_topic("Approval(address,address,uint256)"),
+            B256::from(hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"))
+        );
+    }
+}
@@ -2,11 +2,14 @@ use jsonrpsee::core::RpcResult as Result;
 use jsonrpsee::proc_macros::rpc;
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64};
 use reth_rpc_types::serde_helpers::JsonStorageKey;
+use reth_rpc_types::state::StateOverride;
 use reth_rpc_types::{
-    AccessListWithGasUsed, EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Index, RichBlock,
-    SyncStatus, Transaction as EthTransaction, TransactionReceipt, TransactionRequest, Work,
+    AccessListWithGasUsed, BlockOverrides, EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Index,
+    RichBlock, SyncStatus, Transaction as EthTransaction, TransactionRequest, Work,
 };
 
+use crate::models::receipt::KakarotTransactionReceipt;
+
 /// Ethereum JSON-RPC API Trait
 /// Mostly based on <https://github.com/paradigmxyz/reth/blob/559124ac5a0b25030250203babcd8a94693df648/crates/rpc/rpc-api/src/eth.rs#L15>
 /// With some small modifications
@@ -84,9 +87,25 @@ pub trait EthApi {
         index: Index,
     ) -> Result<Option<EthTransaction>>;
 
+    /// Returns the EIP-2718 binary-encoded transaction requested by transaction hash.
+    #[method(name = "getRawTransactionByHash")]
+    async fn raw_transaction_by_hash(&self, hash: B256) -> Result<Option<Bytes>>;
+
+    /// Returns the EIP-2718 binary-encoded transaction by block hash and transaction index position.
+    #[method(name = "getRawTransactionByBlockHashAndIndex")]
+    async fn raw_transaction_by_block_hash_and_index(&self, hash: B256, index: Index) -> Result<Option<Bytes>>;
+
+    /// Returns the EIP-2718 binary-encoded transaction by block number and transaction index position.
+    #[method(name = "getRawTransactionByBlockNumberAndIndex")]
+    async fn raw_transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumberOrTag,
+        index: Index,
+    ) -> Result<Option<Bytes>>;
+
     /// Returns the receipt of a transaction by transaction hash.
     #[method(name = "getTransactionReceipt")]
-    async fn transaction_receipt(&self, hash: B256) -> Result<Option<TransactionReceipt>>;
+    async fn transaction_receipt(&self, hash: B256) -> Result<Option<KakarotTransactionReceipt>>;
 
     /// Returns the balance of the account of given address.
     #[method(name = "getBalance")]
@@ -109,8 +128,18 @@ pub trait EthApi {
     async fn get_logs(&self, filter: Filter) -> Result<FilterChanges>;
 
     /// Executes a new message call immediately without creating a transaction on the block chain.
+    /// State overrides are not supported by the Kakarot execution entrypoint, and a call
+    /// with a non empty set of overrides is rejected. Of the block overrides, only the base
+    /// fee is supported: the call pays the gas price it would pay under this base fee, while
+    /// the execution context keeps the base fee of Kakarot.
     #[method(name = "call")]
-    async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>) -> Result<Bytes>;
+    async fn call(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> Result<Bytes>;
 
     /// Generates an access list for a transaction.
     ///
@@ -245,5 +274,5 @@ pub trait EthApi {
 
     /// Returns all transaction receipts for a given block.
     #[method(name = "getBlockReceipts")]
-    async fn block_receipts(&self, block_id: Option<BlockId>) -> Result<Option<Vec<TransactionReceipt>>>;
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> Result<Option<Vec<KakarotTransactionReceipt>>>;
 }
@@ -0,0 +1,44 @@
+use crate::models::log::{LogCursor, PaginatedLogs};
+use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::proc_macros::rpc;
+use reth_primitives::{Address, B256, U256, U64};
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use reth_rpc_types::{BlockId, Filter};
+use starknet::core::types::MaybePendingBlockWithTxs;
+
+/// Kakarot specific extensions of the Ethereum JSON-RPC API.
+#[rpc(server, namespace = "kakarot")]
+#[async_trait]
+pub trait KakarotApi {
+    /// Returns a page of the logs matching the filter, starting after the cursor if any.
+    /// The returned cursor should be passed to the next call in order to resume the query,
+    /// and is null once all the logs have been returned.
+    #[method(name = "getLogsPaginated")]
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        page_size: Option<U64>,
+    ) -> Result<PaginatedLogs>;
+
+    /// Returns the values of the storage slots of an address at the given block, in the order
+    /// of the slots. The slots are read concurrently, all from the same block.
+    #[method(name = "getStorageAtBatch")]
+    async fn storage_at_batch(
+        &self,
+        address: Address,
+        slots: Vec<JsonStorageKey>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<B256>>;
+
+    /// Returns the balances of the addresses at the given block, in the order of the addresses.
+    /// The balances are read concurrently, all from the same block. The balance of an address
+    /// without a deployed account is zero.
+    #[method(name = "getBalances")]
+    async fn balances(&self, addresses: Vec<Address>, block_id: Option<BlockId>) -> Result<Vec<U256>>;
+
+    /// Returns the underlying Starknet block with its transactions, as returned by the Starknet node.
+    /// Debug method, only available when the debug endpoints are enabled.
+    #[method(name = "getStarknetBlock")]
+    async fn get_starknet_block(&self, block_id: Option<BlockId>) -> Result<MaybePendingBlockWithTxs>;
+}
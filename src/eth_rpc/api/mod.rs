@@ -1,6 +1,7 @@
 pub mod alchemy_api;
 pub mod debug_api;
 pub mod eth_api;
+pub mod kakarot_api;
 pub mod net_api;
 pub mod trace_api;
 pub mod txpool_api;
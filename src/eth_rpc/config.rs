@@ -1,19 +1,179 @@
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use eyre::{eyre, Result};
+use http::{HeaderName, HeaderValue, Method};
+
+use super::middleware::rate_limit::RateLimitConfig;
 
 #[derive(Debug)]
 pub struct RPCConfig {
     pub socket_addr: String,
+    /// Path of the Unix domain socket for the IPC server, disabled if not set.
+    pub ipc_path: Option<PathBuf>,
+    /// Time given to the in-flight requests to complete on shutdown.
+    pub shutdown_grace_period: Duration,
+    /// Rate limit applied to each client, disabled if not set.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Maximum number of calls in a batch request, unlimited if not set.
+    pub max_batch_size: Option<u32>,
+    /// Minimum size in bytes of the compressed responses, compression is disabled if not set.
+    pub compression_min_size: Option<u16>,
+    /// CORS policy of the HTTP server.
+    pub cors: CorsConfig,
+    /// Time after which the handler of a call is aborted, unlimited if not set.
+    pub request_timeout: Option<Duration>,
+    /// Duration above which a completed call is logged as slow, disabled if not set.
+    pub slow_request_threshold: Option<Duration>,
 }
 
+/// CORS policy of the HTTP server. Credentials are never allowed, so the default policy
+/// lets any origin send JSON-RPC requests, as for any public, unauthenticated, endpoint.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to make requests, any origin if not set.
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+    /// Methods allowed in the requests.
+    pub allowed_methods: Vec<Method>,
+    /// Headers allowed in the requests.
+    pub allowed_headers: Vec<HeaderName>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: None,
+            allowed_methods: vec![Method::GET, Method::POST],
+            allowed_headers: vec![http::header::CONTENT_TYPE],
+        }
+    }
+}
+
+/// Default time given to the in-flight requests to complete on shutdown.
+const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
 impl RPCConfig {
     pub const fn new(socket_addr: String) -> Self {
-        Self { socket_addr }
+        Self {
+            socket_addr,
+            ipc_path: None,
+            shutdown_grace_period: DEFAULT_SHUTDOWN_GRACE_PERIOD,
+            rate_limit: None,
+            max_batch_size: None,
+            compression_min_size: None,
+            cors: CorsConfig::default(),
+            request_timeout: None,
+            slow_request_threshold: None,
+        }
+    }
+
+    /// Sets the maximum number of calls in a batch request.
+    #[must_use]
+    pub const fn with_max_batch_size(mut self, max_batch_size: u32) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Enables the compression of the responses of at least `min_size` bytes.
+    #[must_use]
+    pub const fn with_compression(mut self, min_size: u16) -> Self {
+        self.compression_min_size = Some(min_size);
+        self
+    }
+
+    /// Sets the time after which the handler of a call is aborted.
+    #[must_use]
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Sets the duration above which a completed call is logged as slow.
+    #[must_use]
+    pub const fn with_slow_request_threshold(mut self, slow_request_threshold: Duration) -> Self {
+        self.slow_request_threshold = Some(slow_request_threshold);
+        self
+    }
+
+    /// Sets the CORS policy of the HTTP server.
+    #[must_use]
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    /// Sets the rate limit applied to each client.
+    #[must_use]
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Sets the time given to the in-flight requests to complete on shutdown.
+    #[must_use]
+    pub const fn with_shutdown_grace_period(mut self, shutdown_grace_period: Duration) -> Self {
+        self.shutdown_grace_period = shutdown_grace_period;
+        self
+    }
+
+    /// Sets the path of the IPC socket.
+    #[must_use]
+    pub fn with_ipc_path(mut self, ipc_path: impl Into<PathBuf>) -> Self {
+        self.ipc_path = Some(ipc_path.into());
+        self
     }
 
     pub fn from_env() -> Result<Self> {
         let socket_addr = std::env::var("KAKAROT_RPC_URL")
             .map_err(|_| eyre!("Missing mandatory environment variable: KAKAROT_RPC_URL"))?;
-        Ok(Self::new(socket_addr))
+        let ipc_path = std::env::var("KAKAROT_RPC_IPC_PATH").ok().filter(|path| !path.is_empty()).map(PathBuf::from);
+        let shutdown_grace_period = std::env::var("SHUTDOWN_GRACE_PERIOD_SECS")
+            .ok()
+            .filter(|secs| !secs.is_empty())
+            .map(|secs| secs.parse().map(Duration::from_secs))
+            .transpose()
+            .map_err(|err| eyre!("Invalid SHUTDOWN_GRACE_PERIOD_SECS: {err}"))?
+            .unwrap_or(DEFAULT_SHUTDOWN_GRACE_PERIOD);
+        let rate_limit = rate_limit_from_env()?;
+        let max_batch_size = std::env::var("RPC_MAX_BATCH_SIZE")
+            .ok()
+            .filter(|size| !size.is_empty())
+            .map(|size| size.parse())
+            .transpose()
+            .map_err(|err| eyre!("Invalid RPC_MAX_BATCH_SIZE: {err}"))?;
+        let compression_min_size = std::env::var("RPC_COMPRESSION_MIN_SIZE")
+            .ok()
+            .filter(|size| !size.is_empty())
+            .map(|size| size.parse())
+            .transpose()
+            .map_err(|err| eyre!("Invalid RPC_COMPRESSION_MIN_SIZE: {err}"))?;
+        let cors = cors_from_env()?;
+        let request_timeout = std::env::var("RPC_REQUEST_TIMEOUT_SECS")
+            .ok()
+            .filter(|secs| !secs.is_empty())
+            .map(|secs| secs.parse().map(Duration::from_secs))
+            .transpose()
+            .map_err(|err| eyre!("Invalid RPC_REQUEST_TIMEOUT_SECS: {err}"))?;
+        let slow_request_threshold = std::env::var("RPC_SLOW_REQUEST_MILLIS")
+            .ok()
+            .filter(|millis| !millis.is_empty())
+            .map(|millis| millis.parse().map(Duration::from_millis))
+            .transpose()
+            .map_err(|err| eyre!("Invalid RPC_SLOW_REQUEST_MILLIS: {err}"))?;
+        Ok(Self {
+            socket_addr,
+            ipc_path,
+            shutdown_grace_period,
+            rate_limit,
+            max_batch_size,
+            compression_min_size,
+            cors,
+            request_timeout,
+            slow_request_threshold,
+        })
     }
 
     pub fn from_port(port: u16) -> Result<Self> {
@@ -43,3 +203,64 @@ impl RPCConfig {
         config
     }
 }
+
+/// Reads the rate limit configuration from the environment. The rate limit is
+/// enabled by setting `RATE_LIMIT_PER_SECOND`.
+fn rate_limit_from_env() -> Result<Option<RateLimitConfig>> {
+    let Some(requests_per_second) = std::env::var("RATE_LIMIT_PER_SECOND").ok().filter(|rate| !rate.is_empty()) else {
+        return Ok(None);
+    };
+    let requests_per_second: NonZeroU32 =
+        requests_per_second.parse().map_err(|err| eyre!("Invalid RATE_LIMIT_PER_SECOND: {err}"))?;
+    let burst = std::env::var("RATE_LIMIT_BURST")
+        .ok()
+        .filter(|burst| !burst.is_empty())
+        .map(|burst| burst.parse::<NonZeroU32>())
+        .transpose()
+        .map_err(|err| eyre!("Invalid RATE_LIMIT_BURST: {err}"))?
+        .unwrap_or(requests_per_second);
+    let allowlist = ips_from_env("RATE_LIMIT_ALLOWLIST")?;
+    let trusted_proxies = ips_from_env("RATE_LIMIT_TRUSTED_PROXIES")?;
+
+    Ok(Some(RateLimitConfig { requests_per_second, burst, allowlist, trusted_proxies }))
+}
+
+/// Reads a comma separated list of IPs from the environment.
+fn ips_from_env(name: &str) -> Result<HashSet<IpAddr>> {
+    std::env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|err| eyre!("Invalid {name}: {err}"))
+}
+
+/// Reads the CORS policy from the comma separated lists of `CORS_ALLOWED_ORIGINS`,
+/// `CORS_ALLOWED_METHODS` and `CORS_ALLOWED_HEADERS`. Unset variables keep the default policy.
+fn cors_from_env() -> Result<CorsConfig> {
+    fn list_from_env<T>(name: &str, parse: impl Fn(&str) -> Result<T>) -> Result<Option<Vec<T>>> {
+        let Some(list) = std::env::var(name).ok().filter(|list| !list.trim().is_empty()) else {
+            return Ok(None);
+        };
+        list.split(',').map(str::trim).filter(|item| !item.is_empty()).map(parse).collect::<Result<_>>().map(Some)
+    }
+
+    let default = CorsConfig::default();
+    // A wildcard keeps any origin allowed
+    let allowed_origins = list_from_env("CORS_ALLOWED_ORIGINS", |origin| {
+        HeaderValue::from_str(origin).map_err(|err| eyre!("Invalid CORS_ALLOWED_ORIGINS: {err}"))
+    })?
+    .filter(|origins| !origins.iter().any(|origin| origin == "*"));
+    let allowed_methods = list_from_env("CORS_ALLOWED_METHODS", |method| {
+        Method::from_bytes(method.as_bytes()).map_err(|err| eyre!("Invalid CORS_ALLOWED_METHODS: {err}"))
+    })?
+    .unwrap_or(default.allowed_methods);
+    let allowed_headers = list_from_env("CORS_ALLOWED_HEADERS", |header| {
+        HeaderName::from_bytes(header.as_bytes()).map_err(|err| eyre!("Invalid CORS_ALLOWED_HEADERS: {err}"))
+    })?
+    .unwrap_or(default.allowed_headers);
+
+    Ok(CorsConfig { allowed_origins, allowed_methods, allowed_headers })
+}
@@ -0,0 +1,176 @@
+//! IPC transport for the Kakarot RPC, serving the same methods as the HTTP/WS server
+//! over a Unix domain socket. Requests and responses are newline delimited JSON.
+//!
+//! Only the maximum batch size applies to the IPC requests: the rate limit and the RPC
+//! middleware of the HTTP server, i.e. the request timeout, the metrics and the logging of
+//! the slow requests, are HTTP-only, as the IPC calls are executed directly on the methods.
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use jsonrpsee::types::error::{reject_too_big_batch_request, ErrorCode};
+use jsonrpsee::types::ErrorObject;
+use jsonrpsee::RpcModule;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::RpcError;
+
+/// Size of the buffer used for the notifications of a single request.
+const IPC_NOTIFICATIONS_BUFFER_SIZE: usize = 16;
+
+/// Handle to a running IPC server.
+#[derive(Debug)]
+pub struct IpcServerHandle {
+    path: PathBuf,
+    stop: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl IpcServerHandle {
+    /// Returns the path of the socket the server listens on.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops the server, waiting up to the grace period for the in-flight requests of the open
+    /// connections to complete, and removes the socket file. Returns whether the requests were
+    /// drained, the remaining ones being aborted otherwise.
+    pub async fn stop(mut self, grace_period: Duration) -> bool {
+        let _ = self.stop.send(true);
+        let drained = tokio::time::timeout(grace_period, &mut self.task).await.is_ok();
+        if !drained {
+            tracing::warn!("in-flight IPC requests did not complete within {grace_period:?}, aborting them");
+            self.task.abort();
+            if let Err(err) = std::fs::remove_file(&self.path) {
+                tracing::warn!("failed to remove IPC socket {}: {err}", self.path.display());
+            }
+        }
+        drained
+    }
+}
+
+/// Starts an IPC server listening on the given socket path, rejecting the batch requests
+/// of more than `max_batch_size` calls if set.
+///
+/// # Errors
+///
+/// Will return `Err` if the socket can't be bound to the path.
+pub fn run_ipc_server(
+    module: RpcModule<()>,
+    path: impl Into<PathBuf>,
+    max_batch_size: Option<u32>,
+) -> Result<IpcServerHandle, RpcError> {
+    let path = path.into();
+    // Remove a stale socket left by a previous run
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    let listener = UnixListener::bind(&path)?;
+    let (stop, mut stopped) = watch::channel(false);
+
+    let socket_path = path.clone();
+    let task = tokio::spawn(async move {
+        let mut connections = JoinSet::new();
+        loop {
+            tokio::select! {
+                _ = stopped.changed() => break,
+                // Reaps the closed connections
+                Some(_) = connections.join_next(), if !connections.is_empty() => {}
+                connection = listener.accept() => match connection {
+                    Ok((stream, _)) => {
+                        connections.spawn(serve_connection(stream, module.clone(), stopped.clone(), max_batch_size));
+                    }
+                    Err(err) => tracing::error!("failed to accept IPC connection: {err}"),
+                },
+            }
+        }
+        // The connections stop reading requests once stopped, after answering the in-flight ones
+        while connections.join_next().await.is_some() {}
+        if let Err(err) = std::fs::remove_file(&socket_path) {
+            tracing::warn!("failed to remove IPC socket {}: {err}", socket_path.display());
+        }
+    });
+
+    Ok(IpcServerHandle { path, stop, task })
+}
+
+/// Serves the requests of a single IPC connection until it is closed or the server stops.
+async fn serve_connection(
+    stream: UnixStream,
+    module: RpcModule<()>,
+    mut stopped: watch::Receiver<bool>,
+    max_batch_size: Option<u32>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        let line = tokio::select! {
+            _ = stopped.changed() => break,
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    tracing::debug!("failed to read IPC request: {err}");
+                    break;
+                }
+            },
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some(response) = handle_request(&module, &line, max_batch_size).await else { continue };
+        if writer.write_all(format!("{response}\n").as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Executes a single or a batch request, returning its response, or `None` if it only holds
+/// notifications, which aren't answered.
+async fn handle_request(module: &RpcModule<()>, request: &str, max_batch_size: Option<u32>) -> Option<String> {
+    match serde_json::from_str::<Value>(request) {
+        Err(err) => {
+            tracing::debug!("invalid IPC request: {err}");
+            Some(error_response(ErrorCode::ParseError.into(), Value::Null))
+        }
+        Ok(Value::Array(calls)) => {
+            if calls.is_empty() {
+                return Some(error_response(ErrorCode::InvalidRequest.into(), Value::Null));
+            }
+            if let Some(max_batch_size) = max_batch_size.filter(|max| calls.len() > *max as usize) {
+                return Some(error_response(reject_too_big_batch_request(max_batch_size as usize), Value::Null));
+            }
+            let mut responses = Vec::with_capacity(calls.len());
+            for call in calls {
+                responses.extend(handle_call(module, call).await);
+            }
+            (!responses.is_empty()).then(|| format!("[{}]", responses.join(",")))
+        }
+        Ok(call) => handle_call(module, call).await,
+    }
+}
+
+/// Executes a single call, returning its response, or `None` for a notification.
+async fn handle_call(module: &RpcModule<()>, call: Value) -> Option<String> {
+    if !call.is_object() {
+        return Some(error_response(ErrorCode::InvalidRequest.into(), Value::Null));
+    }
+    let id = call.get("id")?.clone();
+    match module.raw_json_request(&call.to_string(), IPC_NOTIFICATIONS_BUFFER_SIZE).await {
+        Ok((response, _)) => Some(response),
+        Err(err) => {
+            tracing::debug!("invalid IPC request: {err}");
+            Some(error_response(ErrorCode::InvalidRequest.into(), id))
+        }
+    }
+}
+
+/// Returns the JSON-RPC error response of the request with the given id.
+fn error_response(error: ErrorObject<'_>, id: Value) -> String {
+    serde_json::json!({"jsonrpc": "2.0", "error": error, "id": id}).to_string()
+}
@@ -217,3 +217,38 @@ impl<'a, F: Future<Output = MethodResponse>> Future for ResponseFuture<'a, F> {
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::{ErrorObject, Id};
+    use std::borrow::Cow;
+
+    /// A service answering every call with an error.
+    struct Failing;
+
+    impl<'a> RpcServiceT<'a> for Failing {
+        type Future = std::future::Ready<MethodResponse>;
+
+        fn call(&self, req: Request<'a>) -> Self::Future {
+            std::future::ready(MethodResponse::error(req.id, ErrorObject::owned(-32603, "failure", None::<()>)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_calls() {
+        // Given
+        let registry = Registry::new();
+        let metrics = RpcMetrics::new(Some(&registry)).unwrap().unwrap();
+        let service = Metrics::new(Failing, metrics.clone(), "http");
+
+        // When
+        let _ = service.call(Request::new(Cow::Borrowed("eth_chainId"), None, Id::Number(1))).await;
+
+        // Then
+        assert_eq!(metrics.calls_started.with_label_values(&["http", "eth_chainId"]).get(), 1);
+        assert_eq!(metrics.calls_finished.with_label_values(&["http", "eth_chainId", "true"]).get(), 1);
+        assert_eq!(metrics.calls_finished.with_label_values(&["http", "eth_chainId", "false"]).get(), 0);
+        assert_eq!(metrics.calls_time.with_label_values(&["http", "eth_chainId"]).get_sample_count(), 1);
+    }
+}
@@ -21,4 +21,9 @@
 /// Grafana metrics middleware.
 pub mod metrics;
 /// Rate limit middleware.
+pub mod rate_limit;
+/// Slow request logging middleware.
+pub mod slow_request;
+/// Request timeout middleware.
+pub mod timeout;
 pub use metrics::*;
@@ -0,0 +1,292 @@
+//! HTTP middleware rate limiting the requests by client IP.
+//!
+//! The client IP is the address of the connected peer, which the server stores in the request
+//! extensions. The `X-Forwarded-For` and `X-Real-IP` headers are only trusted when the peer is
+//! one of the configured trusted proxies, in which case the client is the rightmost hop of
+//! `X-Forwarded-For` which isn't a trusted proxy itself.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use governor::clock::DefaultClock;
+use governor::state::keyed::HashMapStateStore;
+use governor::{Quota, RateLimiter};
+use jsonrpsee::server::{HttpRequest, HttpResponse};
+use jsonrpsee::types::ErrorObject;
+
+use crate::eth_provider::error::EthRpcErrorCode;
+
+type KeyedRateLimiter = RateLimiter<IpAddr, HashMapStateStore<IpAddr>, DefaultClock>;
+
+/// Number of requests between two prunings of the clients whose bucket is full again.
+const PRUNE_INTERVAL: u64 = 1024;
+
+/// Configuration of the rate limiter.
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Number of requests allowed per second for each client.
+    pub requests_per_second: NonZeroU32,
+    /// Number of requests a client can make at once.
+    pub burst: NonZeroU32,
+    /// Clients that are never rate limited.
+    pub allowlist: HashSet<IpAddr>,
+    /// Reverse proxies whose forwarding headers are trusted.
+    pub trusted_proxies: HashSet<IpAddr>,
+}
+
+/// Rate limit layer.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limiter: Arc<KeyedRateLimiter>,
+    allowlist: Arc<HashSet<IpAddr>>,
+    trusted_proxies: Arc<HashSet<IpAddr>>,
+    requests: Arc<AtomicU64>,
+}
+
+impl RateLimitLayer {
+    /// Create a new [`RateLimitLayer`].
+    pub fn new(config: RateLimitConfig) -> Self {
+        let quota = Quota::per_second(config.requests_per_second).allow_burst(config.burst);
+        Self {
+            limiter: Arc::new(RateLimiter::hashmap(quota)),
+            allowlist: Arc::new(config.allowlist),
+            trusted_proxies: Arc::new(config.trusted_proxies),
+            requests: Arc::default(),
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimit {
+            service,
+            limiter: self.limiter.clone(),
+            allowlist: self.allowlist.clone(),
+            trusted_proxies: self.trusted_proxies.clone(),
+            requests: self.requests.clone(),
+        }
+    }
+}
+
+/// Rate limit middleware.
+#[derive(Clone, Debug)]
+pub struct RateLimit<S> {
+    service: S,
+    limiter: Arc<KeyedRateLimiter>,
+    allowlist: Arc<HashSet<IpAddr>>,
+    trusted_proxies: Arc<HashSet<IpAddr>>,
+    requests: Arc<AtomicU64>,
+}
+
+impl<S> tower::Service<HttpRequest> for RateLimit<S>
+where
+    S: tower::Service<HttpRequest, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest) -> Self::Future {
+        // Forget the clients which wouldn't be limited anymore, so the state stays bounded.
+        if self.requests.fetch_add(1, Ordering::Relaxed) % PRUNE_INTERVAL == PRUNE_INTERVAL - 1 {
+            self.limiter.retain_recent();
+            self.limiter.shrink_to_fit();
+        }
+
+        let Some(ip) = client_ip(&request, &self.trusted_proxies) else {
+            // Only requests which didn't go through the server lack a peer address.
+            return Box::pin(self.service.call(request));
+        };
+        if !self.allowlist.contains(&ip) && self.limiter.check_key(&ip).is_err() {
+            tracing::debug!("rate limit exceeded for {ip}");
+            return Box::pin(std::future::ready(Ok(too_many_requests())));
+        }
+        Box::pin(self.service.call(request))
+    }
+}
+
+/// Returns the IP of the client. Forwarding headers are only read if the peer is a trusted proxy,
+/// skipping the trusted hops from the right of `X-Forwarded-For`.
+fn client_ip(request: &HttpRequest, trusted_proxies: &HashSet<IpAddr>) -> Option<IpAddr> {
+    let peer = request.extensions().get::<SocketAddr>()?.ip();
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    let headers = request.headers();
+    let forwarded = headers.get("x-forwarded-for").and_then(|value| value.to_str().ok());
+    if let Some(forwarded) = forwarded {
+        let mut hops = forwarded.rsplit(',').map(|hop| hop.trim().parse::<IpAddr>());
+        // An unparsable hop can't be trusted, and neither can the hops left of it.
+        return match hops.find(|hop| hop.as_ref().map_or(true, |ip| !trusted_proxies.contains(ip))) {
+            Some(Ok(ip)) => Some(ip),
+            _ => Some(peer),
+        };
+    }
+    headers.get("x-real-ip").and_then(|value| value.to_str().ok()).and_then(|ip| ip.trim().parse().ok()).or(Some(peer))
+}
+
+/// Builds the response returned to a rate limited client.
+fn too_many_requests() -> HttpResponse {
+    let error = ErrorObject::owned(
+        EthRpcErrorCode::RequestLimitExceeded as i32,
+        "rate limit exceeded, retry later",
+        None::<()>,
+    );
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": null, "error": error }).to_string();
+
+    let mut response = HttpResponse::new(body.into());
+    *response.status_mut() = 429_u16.try_into().expect("valid status code");
+    response.headers_mut().insert("content-type", "application/json".parse().expect("valid header value"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::time::Duration;
+    use tower::{Layer, Service};
+
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<HttpRequest> for Echo {
+        type Response = HttpResponse;
+        type Error = Infallible;
+        type Future = std::future::Ready<Result<HttpResponse, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: HttpRequest) -> Self::Future {
+            std::future::ready(Ok(HttpResponse::new(Default::default())))
+        }
+    }
+
+    fn request_from(peer: &str, forwarded_for: Option<&str>) -> HttpRequest {
+        let mut request = HttpRequest::new(Default::default());
+        request.extensions_mut().insert(SocketAddr::new(peer.parse().unwrap(), 40000));
+        if let Some(forwarded_for) = forwarded_for {
+            request.headers_mut().insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        }
+        request
+    }
+
+    async fn status(service: &mut RateLimit<Echo>, peer: &str, forwarded_for: Option<&str>) -> u16 {
+        service.call(request_from(peer, forwarded_for)).await.unwrap().status().as_u16()
+    }
+
+    fn layer() -> RateLimitLayer {
+        RateLimitLayer::new(RateLimitConfig {
+            requests_per_second: NonZeroU32::new(10).unwrap(),
+            burst: NonZeroU32::new(2).unwrap(),
+            allowlist: HashSet::from(["10.0.0.3".parse().unwrap()]),
+            trusted_proxies: HashSet::from(["10.0.0.100".parse().unwrap(), "10.0.0.101".parse().unwrap()]),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        // Given
+        let mut service = layer().layer(Echo);
+
+        // When
+        let mut bursting = Vec::new();
+        for _ in 0..5 {
+            bursting.push(status(&mut service, "10.0.0.1", None).await);
+        }
+        let mut slow = Vec::new();
+        for _ in 0..3 {
+            slow.push(status(&mut service, "10.0.0.2", None).await);
+            tokio::time::sleep(Duration::from_millis(150)).await;
+        }
+        let mut allowlisted = Vec::new();
+        for _ in 0..5 {
+            allowlisted.push(status(&mut service, "10.0.0.3", None).await);
+        }
+
+        // Then
+        assert_eq!(bursting, vec![200, 200, 429, 429, 429]);
+        assert!(slow.iter().all(|status| *status == 200));
+        assert!(allowlisted.iter().all(|status| *status == 200));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_ignores_untrusted_forwarding_headers() {
+        // Given
+        let mut service = layer().layer(Echo);
+
+        // When
+        let mut spoofed = Vec::new();
+        for i in 0..5 {
+            spoofed.push(status(&mut service, "10.0.0.1", Some(&format!("10.0.1.{i}"))).await);
+        }
+        let mut spoofed_allowlist = Vec::new();
+        for _ in 0..3 {
+            spoofed_allowlist.push(status(&mut service, "10.0.0.2", Some("10.0.0.3")).await);
+        }
+
+        // Then
+        assert_eq!(spoofed, vec![200, 200, 429, 429, 429]);
+        assert_eq!(spoofed_allowlist, vec![200, 200, 429]);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_behind_trusted_proxies() {
+        // Given
+        let mut service = layer().layer(Echo);
+
+        // When
+        let mut clients = Vec::new();
+        for i in 0..4 {
+            clients.push(status(&mut service, "10.0.0.100", Some(&format!("10.0.1.{i}, 10.0.0.101"))).await);
+        }
+        // The client prepending a fake hop is still keyed on the address seen by the proxy.
+        let mut spoofed = Vec::new();
+        for i in 0..3 {
+            spoofed.push(status(&mut service, "10.0.0.100", Some(&format!("10.0.2.{i}, 10.0.0.1"))).await);
+        }
+
+        // Then
+        assert!(clients.iter().all(|status| *status == 200));
+        assert_eq!(spoofed, vec![200, 200, 429]);
+    }
+
+    #[test]
+    fn test_client_ip() {
+        // Given
+        let trusted_proxies = HashSet::from(["10.0.0.100".parse().unwrap()]);
+        let mut real_ip = request_from("10.0.0.100", None);
+        real_ip.headers_mut().insert("x-real-ip", "10.0.0.5".parse().unwrap());
+
+        // When
+        let ips = [
+            client_ip(&HttpRequest::new(Default::default()), &trusted_proxies),
+            client_ip(&request_from("10.0.0.1", Some("10.0.0.5")), &trusted_proxies),
+            client_ip(&request_from("10.0.0.100", None), &trusted_proxies),
+            client_ip(&request_from("10.0.0.100", Some("10.0.0.5, 10.0.0.6")), &trusted_proxies),
+            client_ip(&request_from("10.0.0.100", Some("10.0.0.5, garbage")), &trusted_proxies),
+            client_ip(&real_ip, &trusted_proxies),
+        ];
+
+        // Then
+        let expected =
+            [None, Some("10.0.0.1"), Some("10.0.0.100"), Some("10.0.0.6"), Some("10.0.0.100"), Some("10.0.0.5")];
+        assert_eq!(ips, expected.map(|ip| ip.map(|ip| ip.parse::<IpAddr>().unwrap())));
+    }
+}
@@ -0,0 +1,139 @@
+//! JSON-RPC middleware logging the calls which take long to complete.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use jsonrpsee::types::Request;
+use jsonrpsee::MethodResponse;
+use tracing::Instrument;
+
+/// Slow request layer.
+#[derive(Clone, Copy, Debug)]
+pub struct SlowRequestLayer {
+    threshold: Duration,
+}
+
+impl SlowRequestLayer {
+    /// Create a new [`SlowRequestLayer`], logging the calls taking longer than `threshold`.
+    pub const fn new(threshold: Duration) -> Self {
+        Self { threshold }
+    }
+}
+
+impl<S> tower::Layer<S> for SlowRequestLayer {
+    type Service = SlowRequest<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        SlowRequest { service, threshold: self.threshold }
+    }
+}
+
+/// Slow request middleware. Each call is run within a span holding its method name, and
+/// logged at warn level with its duration once completed if it exceeded the threshold.
+#[derive(Clone, Debug)]
+pub struct SlowRequest<S> {
+    service: S,
+    threshold: Duration,
+}
+
+impl<'a, S> RpcServiceT<'a> for SlowRequest<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+    S::Future: Send + 'a,
+{
+    type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let method = req.method_name().to_string();
+        let threshold = self.threshold;
+        let span = tracing::debug_span!("rpc_request", method = %method);
+        let response = self.service.call(req);
+
+        Box::pin(
+            async move {
+                let started = Instant::now();
+                let response = response.await;
+                let duration = started.elapsed();
+                if duration > threshold {
+                    tracing::warn!("slow request {method} took {duration:?}");
+                }
+                response
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::{Id, ResponsePayload};
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+    use tower::Layer;
+
+    /// A service answering every call after the given delay.
+    struct Slow(Duration);
+
+    impl<'a> RpcServiceT<'a> for Slow {
+        type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+        fn call(&self, req: Request<'a>) -> Self::Future {
+            let delay = self.0;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                MethodResponse::response(req.id, ResponsePayload::result("0x1"), usize::MAX)
+            })
+        }
+    }
+
+    /// A writer collecting the formatted logs.
+    #[derive(Clone, Default)]
+    struct Logs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn request() -> Request<'static> {
+        Request::new(Cow::Borrowed("eth_chainId"), None, Id::Number(1))
+    }
+
+    #[tokio::test]
+    async fn test_slow_request() {
+        // Given
+        let logs = Logs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .with_max_level(tracing::Level::WARN)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let layer = SlowRequestLayer::new(Duration::from_millis(50));
+        let slow = layer.layer(Slow(Duration::from_millis(100)));
+        let fast = layer.layer(Slow(Duration::ZERO));
+
+        // When
+        let fast_response = fast.call(request()).await;
+        let fast_logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let slow_response = slow.call(request()).await;
+        let slow_logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+
+        // Then
+        assert!(fast_response.is_success());
+        assert!(fast_logs.is_empty());
+        assert!(slow_response.is_success());
+        assert!(slow_logs.contains("WARN"));
+        assert!(slow_logs.contains("slow request eth_chainId took"));
+    }
+}
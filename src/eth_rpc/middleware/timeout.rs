@@ -0,0 +1,109 @@
+//! JSON-RPC middleware aborting the calls which take too long to complete.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+use jsonrpsee::types::{ErrorObject, Request};
+use jsonrpsee::MethodResponse;
+
+use crate::eth_provider::error::EthRpcErrorCode;
+
+/// Request timeout layer.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestTimeoutLayer {
+    timeout: Duration,
+}
+
+impl RequestTimeoutLayer {
+    /// Create a new [`RequestTimeoutLayer`], aborting the calls taking longer than `timeout`.
+    pub const fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> tower::Layer<S> for RequestTimeoutLayer {
+    type Service = RequestTimeout<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RequestTimeout { service, timeout: self.timeout }
+    }
+}
+
+/// Request timeout middleware. A call exceeding the timeout is dropped, which aborts its
+/// handler, and answered with a timeout error.
+#[derive(Clone, Debug)]
+pub struct RequestTimeout<S> {
+    service: S,
+    timeout: Duration,
+}
+
+impl<'a, S> RpcServiceT<'a> for RequestTimeout<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+    S::Future: Send + 'a,
+{
+    type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let id = req.id.clone();
+        let timeout = self.timeout;
+        let response = self.service.call(req);
+
+        Box::pin(async move {
+            tokio::time::timeout(timeout, response).await.unwrap_or_else(|_| {
+                tracing::debug!("request {id:?} timed out after {timeout:?}");
+                MethodResponse::error(
+                    id,
+                    ErrorObject::owned(EthRpcErrorCode::ResourceUnavailable as i32, "request timed out", None::<()>),
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::{Id, ResponsePayload};
+    use std::borrow::Cow;
+    use tower::Layer;
+
+    /// A service answering every call after the given delay.
+    struct Slow(Duration);
+
+    impl<'a> RpcServiceT<'a> for Slow {
+        type Future = Pin<Box<dyn Future<Output = MethodResponse> + Send + 'a>>;
+
+        fn call(&self, req: Request<'a>) -> Self::Future {
+            let delay = self.0;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                MethodResponse::response(req.id, ResponsePayload::result("0x1"), usize::MAX)
+            })
+        }
+    }
+
+    fn request() -> Request<'static> {
+        Request::new(Cow::Borrowed("eth_chainId"), None, Id::Number(1))
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        // Given
+        let layer = RequestTimeoutLayer::new(Duration::from_millis(50));
+        let slow = layer.layer(Slow(Duration::from_secs(5)));
+        let fast = layer.layer(Slow(Duration::ZERO));
+
+        // When
+        let aborted = slow.call(request()).await;
+        let completed = fast.call(request()).await;
+
+        // Then
+        assert!(!aborted.is_success());
+        assert!(aborted.as_result().contains(&(EthRpcErrorCode::ResourceUnavailable as i32).to_string()));
+        assert!(aborted.as_result().contains("request timed out"));
+        assert!(completed.is_success());
+    }
+}
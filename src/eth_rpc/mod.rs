@@ -1,31 +1,48 @@
 // //! Kakarot RPC module for Ethereum.
 // //! It is an adapter layer to interact with Kakarot ZK-EVM.
 use std::net::{AddrParseError, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
 
 use config::RPCConfig;
 pub mod api;
 pub mod config;
+pub mod ipc;
 pub mod middleware;
 pub mod rpc;
 pub mod servers;
 
 use crate::eth_rpc::middleware::metrics::RpcMetrics;
+use crate::eth_rpc::middleware::rate_limit::RateLimitLayer;
+use crate::eth_rpc::middleware::slow_request::SlowRequestLayer;
+use crate::eth_rpc::middleware::timeout::RequestTimeoutLayer;
 use crate::eth_rpc::middleware::MetricsLayer;
 use crate::prometheus_handler::init_prometheus;
 use eyre::Result;
+use http::{Extensions, HeaderMap, StatusCode, Version};
+use hyper_jsonrpsee::server::conn::AddrStream;
+use hyper_jsonrpsee::service::make_service_fn;
 use jsonrpsee::server::middleware::http::{InvalidPath, ProxyGetRequestLayer};
-use jsonrpsee::server::{RpcServiceBuilder, ServerBuilder, ServerHandle};
-use jsonrpsee::RpcModule;
+use jsonrpsee::server::{
+    stop_channel, BatchRequestConfig, HttpRequest, RpcServiceBuilder, ServerBuilder, ServerHandle,
+};
+use jsonrpsee::{Methods, RpcModule};
 use prometheus::Registry;
 use thiserror::Error;
+use tokio::sync::Semaphore;
+use tower::ServiceExt;
 
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::compression::predicate::SizeAbove;
+use tower_http::compression::{CompressionLayer, Predicate};
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
 #[derive(Error, Debug)]
 pub enum RpcError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
+    HyperError(#[from] hyper_jsonrpsee::Error),
+    #[error(transparent)]
     ParseError(#[from] AddrParseError),
     #[error(transparent)]
     JsonRpcError(#[from] InvalidPath),
@@ -37,17 +54,39 @@ pub enum RpcError {
 
 /// # Errors
 ///
-/// Will return `Err` if an error occurs when running the `ServerBuilder` start fails.
+/// Will return `Err` if the server fails to bind to the socket address.
 pub async fn run_server(
     kakarot_rpc_module: RpcModule<()>,
     rpc_config: RPCConfig,
 ) -> Result<(SocketAddr, ServerHandle), RpcError> {
-    let RPCConfig { socket_addr } = rpc_config;
+    let RPCConfig {
+        socket_addr,
+        rate_limit,
+        max_batch_size,
+        compression_min_size,
+        cors,
+        request_timeout,
+        slow_request_threshold,
+        ..
+    } = rpc_config;
+
+    let cors = CorsLayer::new()
+        .allow_methods(cors.allowed_methods)
+        .allow_origin(cors.allowed_origins.map_or_else(AllowOrigin::any, AllowOrigin::list))
+        .allow_headers(cors.allowed_headers);
 
-    let cors = CorsLayer::new().allow_methods(Any).allow_origin(Any).allow_headers(Any);
+    // The encoding is negotiated with the `Accept-Encoding` header of the request. The layer is
+    // always set, the responses keeping the same body type, and never compresses if disabled.
+    let compression = CompressionLayer::new().compress_when(
+        SizeAbove::new(compression_min_size.unwrap_or_default())
+            .and(move |_: StatusCode, _: Version, _: &HeaderMap, _: &Extensions| compression_min_size.is_some()),
+    );
 
-    let http_middleware =
-        tower::ServiceBuilder::new().layer(ProxyGetRequestLayer::new("/health", "net_health")?).layer(cors);
+    let http_middleware = tower::ServiceBuilder::new()
+        .layer(compression)
+        .option_layer(rate_limit.map(RateLimitLayer::new))
+        .layer(ProxyGetRequestLayer::new("/health", "net_health")?)
+        .layer(cors);
 
     // Creating the prometheus registry to register the metrics
     let registry = Registry::new();
@@ -67,19 +106,64 @@ pub async fn run_server(
     // add the metrics as a middleware to the RPC so that every new RPC call fires prometheus metrics
     // upon start, finish etc. we don't need to manually handle each method, it should automatically
     // work for any new method.
-    let rpc_middleware = RpcServiceBuilder::new().option_layer(metrics);
+    let rpc_middleware = RpcServiceBuilder::new()
+        .option_layer(metrics)
+        .option_layer(slow_request_threshold.map(SlowRequestLayer::new))
+        .option_layer(request_timeout.map(RequestTimeoutLayer::new));
 
-    let server = ServerBuilder::default()
-        .max_connections(get_env_or_default("RPC_MAX_CONNECTIONS", "100").parse().unwrap())
+    let max_connections: u32 = get_env_or_default("RPC_MAX_CONNECTIONS", "100").parse().unwrap();
+    let service_builder = ServerBuilder::default()
+        .max_connections(max_connections)
         .set_http_middleware(http_middleware)
         .set_rpc_middleware(rpc_middleware)
-        .build(socket_addr.parse::<SocketAddr>()?)
-        .await?;
+        .set_batch_request_config(max_batch_size.map_or(BatchRequestConfig::Unlimited, BatchRequestConfig::Limit))
+        .to_service_builder();
+
+    // The connections are accepted here rather than by the jsonrpsee server, in order to store
+    // the peer address in the extensions of each request for the rate limiter.
+    let (stop_handle, server_handle) = stop_channel();
+    let methods: Methods = kakarot_rpc_module.into();
+    let connections = Arc::new(Semaphore::new(max_connections as usize));
+    let shutdown = stop_handle.clone();
+    let make_service = make_service_fn(move |connection: &AddrStream| {
+        let peer = connection.remote_addr();
+        let permit = connections.clone().try_acquire_owned();
+        let service = service_builder.clone().build(methods.clone(), stop_handle.clone());
+        async move {
+            let permit = permit.map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "too many connections"))?;
+            Ok::<_, std::io::Error>(service.map_request(move |mut request: HttpRequest| {
+                // The permit is released once the connection is closed and the service dropped.
+                let _ = &permit;
+                request.extensions_mut().insert(peer);
+                request
+            }))
+        }
+    });
 
-    let addr = server.local_addr()?;
-    let handle = server.start(kakarot_rpc_module);
+    let server = hyper_jsonrpsee::Server::try_bind(&socket_addr.parse::<SocketAddr>()?)?.serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(async move {
+        if let Err(err) = server.with_graceful_shutdown(shutdown.shutdown()).await {
+            tracing::error!("RPC server failed: {err}");
+        }
+    });
+
+    Ok((addr, server_handle))
+}
 
-    Ok((addr, handle))
+/// Stops the server from accepting new connections and waits for the in-flight requests
+/// to complete, up to the grace period. Returns false if the grace period elapsed before
+/// all the requests completed, in which case the remaining ones are dropped.
+pub async fn shutdown_server(server_handle: ServerHandle, grace_period: Duration) -> bool {
+    if server_handle.stop().is_err() {
+        // The server was already stopped
+        return true;
+    }
+    let drained = tokio::time::timeout(grace_period, server_handle.stopped()).await.is_ok();
+    if !drained {
+        tracing::warn!("in-flight requests did not complete within {grace_period:?}, aborting them");
+    }
+    drained
 }
 
 fn get_env_or_default(name: &str, default: &str) -> String {
@@ -9,6 +9,7 @@ use crate::eth_provider::provider::EthereumProvider;
 use crate::eth_rpc::api::alchemy_api::AlchemyApiServer;
 use crate::eth_rpc::api::debug_api::DebugApiServer;
 use crate::eth_rpc::api::eth_api::EthApiServer;
+use crate::eth_rpc::api::kakarot_api::KakarotApiServer;
 use crate::eth_rpc::api::net_api::NetApiServer;
 use crate::eth_rpc::api::trace_api::TraceApiServer;
 use crate::eth_rpc::api::txpool_api::TxPoolApiServer;
@@ -16,6 +17,7 @@ use crate::eth_rpc::api::web3_api::Web3ApiServer;
 use crate::eth_rpc::servers::alchemy_rpc::AlchemyRpc;
 use crate::eth_rpc::servers::debug_rpc::DebugRpc;
 use crate::eth_rpc::servers::eth_rpc::KakarotEthRpc;
+use crate::eth_rpc::servers::kakarot_rpc::KakarotRpc;
 use crate::eth_rpc::servers::net_rpc::NetRpc;
 use crate::eth_rpc::servers::trace_rpc::TraceRpc;
 use crate::eth_rpc::servers::txpool_rpc::TxpoolRpc;
@@ -31,6 +33,7 @@ pub enum KakarotRpcModule {
     Debug,
     Trace,
     Txpool,
+    Kakarot,
 }
 
 #[derive(Debug)]
@@ -54,7 +57,8 @@ where
         let net_rpc_module = NetRpc::new(eth_provider.clone()).into_rpc();
         let debug_rpc_module = DebugRpc::new(eth_provider.clone()).into_rpc();
         let trace_rpc_module = TraceRpc::new(eth_provider.clone()).into_rpc();
-        let txpool_rpc_module = TxpoolRpc::new(eth_provider).into_rpc();
+        let txpool_rpc_module = TxpoolRpc::new(eth_provider.clone()).into_rpc();
+        let kakarot_rpc_module = KakarotRpc::new(eth_provider).into_rpc();
 
         let mut modules = HashMap::new();
 
@@ -65,6 +69,7 @@ where
         modules.insert(KakarotRpcModule::Debug, debug_rpc_module.into());
         modules.insert(KakarotRpcModule::Trace, trace_rpc_module.into());
         modules.insert(KakarotRpcModule::Txpool, txpool_rpc_module.into());
+        modules.insert(KakarotRpcModule::Kakarot, kakarot_rpc_module.into());
 
         Self { modules, _phantom: PhantomData }
     }
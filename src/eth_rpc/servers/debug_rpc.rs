@@ -3,13 +3,14 @@ use std::sync::Arc;
 
 use alloy_rlp::Encodable;
 use jsonrpsee::core::{async_trait, RpcResult as Result};
-use reth_primitives::{Block, Bytes, Header, Log, Receipt, ReceiptWithBloom, TransactionSigned, B256};
+use reth_primitives::{Block, Bytes, Header, Log, Receipt, ReceiptWithBloom, B256};
 use reth_rpc_types::trace::geth::{GethDebugTracingOptions, GethTrace, TraceResult};
 use reth_rpc_types::{BlockId, BlockNumberOrTag};
 
-use crate::eth_provider::error::{EthApiError, EthereumDataFormatError, SignatureError};
+use crate::eth_provider::error::{EthApiError, EthereumDataFormatError};
 use crate::eth_provider::provider::EthereumProvider;
 use crate::eth_rpc::api::debug_api::DebugApiServer;
+use crate::models::transaction::rpc_transaction_to_raw;
 use crate::tracing::builder::TracerBuilder;
 
 /// The RPC module for the implementing Net api
@@ -66,24 +67,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugApiServer for DebugRpc<P>
     #[tracing::instrument(skip(self), err, fields(hash = ?hash))]
     async fn raw_transaction(&self, hash: B256) -> Result<Option<Bytes>> {
         let transaction = self.eth_provider.transaction_by_hash(hash).await?;
-
-        if let Some(tx) = transaction {
-            let signature = tx.signature.ok_or_else(|| EthApiError::from(SignatureError::MissingSignature))?;
-            let tx =
-                tx.try_into().map_err(|_| EthApiError::EthereumDataFormat(EthereumDataFormatError::PrimitiveError))?;
-            let bytes = TransactionSigned::from_transaction_and_signature(
-                tx,
-                reth_primitives::Signature {
-                    r: signature.r,
-                    s: signature.s,
-                    odd_y_parity: signature.y_parity.unwrap_or(reth_rpc_types::Parity(false)).0,
-                },
-            )
-            .envelope_encoded();
-            Ok(Some(bytes))
-        } else {
-            Ok(None)
-        }
+        Ok(transaction.map(rpc_transaction_to_raw).transpose()?)
     }
 
     /// Returns an array of EIP-2718 binary-encoded transactions for the given [BlockId].
@@ -93,19 +77,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugApiServer for DebugRpc<P>
         let mut raw_transactions = Vec::with_capacity(transactions.len());
 
         for t in transactions {
-            let signature = t.signature.ok_or_else(|| EthApiError::from(SignatureError::MissingSignature))?;
-            let tx =
-                t.try_into().map_err(|_| EthApiError::EthereumDataFormat(EthereumDataFormatError::PrimitiveError))?;
-            let bytes = TransactionSigned::from_transaction_and_signature(
-                tx,
-                reth_primitives::Signature {
-                    r: signature.r,
-                    s: signature.s,
-                    odd_y_parity: signature.y_parity.unwrap_or(reth_rpc_types::Parity(false)).0,
-                },
-            )
-            .envelope_encoded();
-            raw_transactions.push(bytes);
+            raw_transactions.push(rpc_transaction_to_raw(t)?);
         }
 
         Ok(raw_transactions)
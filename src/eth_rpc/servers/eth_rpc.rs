@@ -3,16 +3,20 @@
 use jsonrpsee::core::{async_trait, RpcResult as Result};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64};
 use reth_rpc_types::serde_helpers::JsonStorageKey;
+use reth_rpc_types::state::StateOverride;
 use reth_rpc_types::{
-    AccessListWithGasUsed, EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Index, RichBlock,
-    SyncStatus, Transaction, TransactionReceipt, TransactionRequest, Work,
+    AccessListWithGasUsed, BlockOverrides, EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Index,
+    RichBlock, SyncStatus, Transaction, TransactionReceipt, TransactionRequest, Work,
 };
 use serde_json::Value;
 
-use crate::eth_provider::constant::MAX_PRIORITY_FEE_PER_GAS;
+use crate::eth_provider::constant::KAKAROT_DEBUG_FIELDS;
 use crate::eth_provider::error::EthApiError;
+use crate::eth_provider::gas::call_request_with_base_fee;
 use crate::eth_provider::provider::EthereumProvider;
 use crate::eth_rpc::api::eth_api::EthApiServer;
+use crate::models::receipt::KakarotTransactionReceipt;
+use crate::models::transaction::rpc_transaction_to_raw;
 
 /// The RPC module for the Ethereum protocol required by Kakarot.
 #[derive(Debug)]
@@ -21,14 +25,32 @@ where
     P: EthereumProvider,
 {
     eth_provider: P,
+    debug_fields: bool,
 }
 
 impl<P> KakarotEthRpc<P>
 where
     P: EthereumProvider,
 {
-    pub const fn new(eth_provider: P) -> Self {
-        Self { eth_provider }
+    pub fn new(eth_provider: P) -> Self {
+        Self { eth_provider, debug_fields: *KAKAROT_DEBUG_FIELDS }
+    }
+
+    /// Enables or disables the non-standard debug fields, e.g. the Starknet transaction hash of receipts.
+    #[must_use]
+    pub const fn with_debug_fields(mut self, debug_fields: bool) -> Self {
+        self.debug_fields = debug_fields;
+        self
+    }
+
+    /// Adds the debug fields to the receipt if they are enabled.
+    async fn kakarot_receipt(&self, receipt: TransactionReceipt) -> Result<KakarotTransactionReceipt> {
+        let starknet_transaction_hash = if self.debug_fields {
+            self.eth_provider.starknet_transaction_hash(receipt.transaction_hash).await?
+        } else {
+            None
+        };
+        Ok(KakarotTransactionReceipt { receipt, starknet_transaction_hash })
     }
 }
 
@@ -48,7 +70,7 @@ where
     }
 
     async fn coinbase(&self) -> Result<Address> {
-        Err(EthApiError::Unsupported("eth_coinbase").into())
+        Err(EthApiError::MethodNotFound("eth_coinbase").into())
     }
 
     #[tracing::instrument(skip_all, ret, err)]
@@ -128,9 +150,34 @@ where
         Ok(self.eth_provider.transaction_by_block_number_and_index(number, index).await?)
     }
 
+    #[tracing::instrument(skip(self), err, fields(hash = %hash))]
+    async fn raw_transaction_by_hash(&self, hash: B256) -> Result<Option<Bytes>> {
+        let transaction = self.eth_provider.transaction_by_hash(hash).await?;
+        Ok(transaction.map(rpc_transaction_to_raw).transpose()?)
+    }
+
+    #[tracing::instrument(skip(self), err, fields(hash = %hash, index = ?index))]
+    async fn raw_transaction_by_block_hash_and_index(&self, hash: B256, index: Index) -> Result<Option<Bytes>> {
+        let transaction = self.eth_provider.transaction_by_block_hash_and_index(hash, index).await?;
+        Ok(transaction.map(rpc_transaction_to_raw).transpose()?)
+    }
+
+    #[tracing::instrument(skip(self), err, fields(number = %number, index = ?index))]
+    async fn raw_transaction_by_block_number_and_index(
+        &self,
+        number: BlockNumberOrTag,
+        index: Index,
+    ) -> Result<Option<Bytes>> {
+        let transaction = self.eth_provider.transaction_by_block_number_and_index(number, index).await?;
+        Ok(transaction.map(rpc_transaction_to_raw).transpose()?)
+    }
+
     #[tracing::instrument(skip(self), ret, err, fields(hash = %hash))]
-    async fn transaction_receipt(&self, hash: B256) -> Result<Option<TransactionReceipt>> {
-        Ok(self.eth_provider.transaction_receipt(hash).await?)
+    async fn transaction_receipt(&self, hash: B256) -> Result<Option<KakarotTransactionReceipt>> {
+        match self.eth_provider.transaction_receipt(hash).await? {
+            Some(receipt) => Ok(Some(self.kakarot_receipt(receipt).await?)),
+            None => Ok(None),
+        }
     }
 
     #[tracing::instrument(skip(self), ret, err, fields(address = %address, block_id = ?block_id))]
@@ -159,7 +206,29 @@ where
     }
 
     #[tracing::instrument(skip(self, request), err, fields(block_id = ?block_id, gas_limit = request.gas))]
-    async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>) -> Result<Bytes> {
+    async fn call(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> Result<Bytes> {
+        if state_overrides.is_some_and(|overrides| !overrides.is_empty()) {
+            return Err(EthApiError::Unsupported("state overrides").into());
+        }
+        let Some(overrides) = block_overrides else {
+            return Ok(self.eth_provider.call(request, block_id).await?);
+        };
+        // Only the base fee can be overridden
+        let base_fee = overrides.base_fee;
+        let other_overrides = BlockOverrides { base_fee: None, ..*overrides };
+        if other_overrides != BlockOverrides::default() {
+            return Err(EthApiError::Unsupported("block overrides other than the base fee").into());
+        }
+        let request = match base_fee {
+            Some(base_fee) => call_request_with_base_fee(request, base_fee)?,
+            None => request,
+        };
         Ok(self.eth_provider.call(request, block_id).await?)
     }
 
@@ -168,7 +237,7 @@ where
         _request: TransactionRequest,
         _block_id: Option<BlockId>,
     ) -> Result<AccessListWithGasUsed> {
-        Err(EthApiError::Unsupported("eth_createAccessList").into())
+        Err(EthApiError::MethodNotFound("eth_createAccessList").into())
     }
 
     #[tracing::instrument(skip(self, request), err, fields(block_id = ?block_id, gas_limit = request.gas))]
@@ -193,11 +262,11 @@ where
 
     #[tracing::instrument(skip_all, ret, err)]
     async fn max_priority_fee_per_gas(&self) -> Result<U256> {
-        Ok(U256::from(*MAX_PRIORITY_FEE_PER_GAS))
+        Ok(self.eth_provider.max_priority_fee_per_gas().await?)
     }
 
     async fn blob_base_fee(&self) -> Result<U256> {
-        Err(EthApiError::Unsupported("eth_blobBaseFee").into())
+        Err(EthApiError::MethodNotFound("eth_blobBaseFee").into())
     }
 
     async fn mining(&self) -> Result<bool> {
@@ -216,15 +285,15 @@ where
     }
 
     async fn submit_hashrate(&self, _hashrate: U256, _id: B256) -> Result<bool> {
-        Err(EthApiError::Unsupported("eth_submitHashrate").into())
+        Err(EthApiError::MethodNotFound("eth_submitHashrate").into())
     }
 
     async fn submit_work(&self, _nonce: B64, _pow_hash: B256, _mix_digest: B256) -> Result<bool> {
-        Err(EthApiError::Unsupported("eth_submitWork").into())
+        Err(EthApiError::MethodNotFound("eth_submitWork").into())
     }
 
     async fn send_transaction(&self, _request: TransactionRequest) -> Result<B256> {
-        Err(EthApiError::Unsupported("eth_sendTransaction").into())
+        Err(EthApiError::MethodNotFound("eth_sendTransaction").into())
     }
 
     #[tracing::instrument(skip_all, ret, err)]
@@ -233,15 +302,15 @@ where
     }
 
     async fn sign(&self, _address: Address, _message: Bytes) -> Result<Bytes> {
-        Err(EthApiError::Unsupported("eth_sign").into())
+        Err(EthApiError::MethodNotFound("eth_sign").into())
     }
 
     async fn sign_transaction(&self, _transaction: TransactionRequest) -> Result<Bytes> {
-        Err(EthApiError::Unsupported("eth_signTransaction").into())
+        Err(EthApiError::MethodNotFound("eth_signTransaction").into())
     }
 
     async fn sign_typed_data(&self, _address: Address, _data: Value) -> Result<Bytes> {
-        Err(EthApiError::Unsupported("eth_signTypedData").into())
+        Err(EthApiError::MethodNotFound("eth_signTypedData").into())
     }
 
     async fn get_proof(
@@ -250,34 +319,39 @@ where
         _keys: Vec<B256>,
         _block_id: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse> {
-        Err(EthApiError::Unsupported("eth_getProof").into())
+        Err(EthApiError::MethodNotFound("eth_getProof").into())
     }
 
     async fn new_filter(&self, _filter: Filter) -> Result<U64> {
-        Err(EthApiError::Unsupported("eth_newFilter").into())
+        Err(EthApiError::MethodNotFound("eth_newFilter").into())
     }
 
     async fn new_block_filter(&self) -> Result<U64> {
-        Err(EthApiError::Unsupported("eth_newBlockFilter").into())
+        Err(EthApiError::MethodNotFound("eth_newBlockFilter").into())
     }
 
     async fn new_pending_transaction_filter(&self) -> Result<U64> {
-        Err(EthApiError::Unsupported("eth_newPendingTransactionFilter").into())
+        Err(EthApiError::MethodNotFound("eth_newPendingTransactionFilter").into())
     }
 
     async fn uninstall_filter(&self, _id: U64) -> Result<bool> {
-        Err(EthApiError::Unsupported("eth_uninstallFilter").into())
+        Err(EthApiError::MethodNotFound("eth_uninstallFilter").into())
     }
 
     async fn get_filter_changes(&self, _id: U64) -> Result<FilterChanges> {
-        Err(EthApiError::Unsupported("eth_getFilterChanges").into())
+        Err(EthApiError::MethodNotFound("eth_getFilterChanges").into())
     }
 
     async fn get_filter_logs(&self, _id: U64) -> Result<FilterChanges> {
-        Err(EthApiError::Unsupported("eth_getFilterLogs").into())
+        Err(EthApiError::MethodNotFound("eth_getFilterLogs").into())
     }
 
-    async fn block_receipts(&self, block_id: Option<BlockId>) -> Result<Option<Vec<TransactionReceipt>>> {
-        Ok(self.eth_provider.block_receipts(block_id).await?)
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> Result<Option<Vec<KakarotTransactionReceipt>>> {
+        let Some(receipts) = self.eth_provider.block_receipts(block_id).await? else { return Ok(None) };
+        let mut kakarot_receipts = Vec::with_capacity(receipts.len());
+        for receipt in receipts {
+            kakarot_receipts.push(self.kakarot_receipt(receipt).await?);
+        }
+        Ok(Some(kakarot_receipts))
     }
 }
@@ -0,0 +1,112 @@
+use futures::{StreamExt, TryStreamExt};
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+use reth_primitives::{Address, BlockNumberOrTag, B256, U256, U64};
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use reth_rpc_types::{BlockId, Filter};
+use starknet::core::types::MaybePendingBlockWithTxs;
+
+use crate::eth_provider::constant::{
+    BATCH_CONCURRENCY, DEFAULT_LOGS_PAGE_SIZE, KAKAROT_DEBUG_ENDPOINTS, KAKAROT_MAX_BATCH_VALUES,
+};
+use crate::eth_provider::error::EthApiError;
+use crate::eth_provider::provider::{EthProviderResult, EthereumProvider};
+use crate::eth_rpc::api::kakarot_api::KakarotApiServer;
+use crate::models::log::{LogCursor, PaginatedLogs};
+
+/// The RPC module for the Kakarot specific extensions.
+#[derive(Debug)]
+pub struct KakarotRpc<P: EthereumProvider> {
+    eth_provider: P,
+    debug_endpoints: bool,
+    max_batch_values: usize,
+}
+
+impl<P: EthereumProvider> KakarotRpc<P> {
+    pub fn new(eth_provider: P) -> Self {
+        Self { eth_provider, debug_endpoints: *KAKAROT_DEBUG_ENDPOINTS, max_batch_values: *KAKAROT_MAX_BATCH_VALUES }
+    }
+
+    /// Enables or disables the debug methods, e.g. `kakarot_getStarknetBlock`.
+    #[must_use]
+    pub const fn with_debug_endpoints(mut self, debug_endpoints: bool) -> Self {
+        self.debug_endpoints = debug_endpoints;
+        self
+    }
+
+    /// Sets the maximum number of values read by a single batched call, e.g. the slots of
+    /// `kakarot_getStorageAtBatch` or the addresses of `kakarot_getBalances`, the calls reading
+    /// more being rejected.
+    #[must_use]
+    pub const fn with_max_batch_values(mut self, max_batch_values: usize) -> Self {
+        self.max_batch_values = max_batch_values;
+        self
+    }
+
+    /// Resolves the latest block into its number once, so that a new block can't be read by
+    /// part of the reads of a batched call.
+    async fn pin_block_id(&self, block_id: Option<BlockId>) -> EthProviderResult<Option<BlockId>> {
+        Ok(match block_id {
+            None | Some(BlockId::Number(BlockNumberOrTag::Latest)) => {
+                Some(BlockId::Number(BlockNumberOrTag::Number(self.eth_provider.block_number().await?.to())))
+            }
+            block_id => block_id,
+        })
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync + 'static> KakarotApiServer for KakarotRpc<P> {
+    #[tracing::instrument(skip_all, err, fields(cursor = ?cursor, page_size = ?page_size))]
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        page_size: Option<U64>,
+    ) -> Result<PaginatedLogs> {
+        let page_size = page_size.map_or(DEFAULT_LOGS_PAGE_SIZE, |size| size.to());
+        Ok(self.eth_provider.get_logs_paginated(filter, cursor, page_size).await?)
+    }
+
+    #[tracing::instrument(skip(self, slots), err, fields(slots = slots.len()))]
+    async fn storage_at_batch(
+        &self,
+        address: Address,
+        slots: Vec<JsonStorageKey>,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<B256>> {
+        if slots.len() > self.max_batch_values {
+            return Err(EthApiError::InvalidParams("too many storage slots").into());
+        }
+        let block_id = self.pin_block_id(block_id).await?;
+
+        let eth_provider = &self.eth_provider;
+        Ok(futures::stream::iter(slots)
+            .map(|slot| eth_provider.storage_at(address, slot, block_id))
+            .buffered(BATCH_CONCURRENCY)
+            .try_collect()
+            .await?)
+    }
+
+    #[tracing::instrument(skip(self, addresses), err, fields(addresses = addresses.len()))]
+    async fn balances(&self, addresses: Vec<Address>, block_id: Option<BlockId>) -> Result<Vec<U256>> {
+        if addresses.len() > self.max_batch_values {
+            return Err(EthApiError::InvalidParams("too many addresses").into());
+        }
+        let block_id = self.pin_block_id(block_id).await?;
+
+        let eth_provider = &self.eth_provider;
+        Ok(futures::stream::iter(addresses)
+            .map(|address| eth_provider.balance(address, block_id))
+            .buffered(BATCH_CONCURRENCY)
+            .try_collect()
+            .await?)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    async fn get_starknet_block(&self, block_id: Option<BlockId>) -> Result<MaybePendingBlockWithTxs> {
+        if !self.debug_endpoints {
+            return Err(EthApiError::MethodNotFound("kakarot_getStarknetBlock").into());
+        }
+        Ok(self.eth_provider.starknet_block(block_id).await?)
+    }
+}
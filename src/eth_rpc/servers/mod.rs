@@ -1,6 +1,7 @@
 pub mod alchemy_rpc;
 pub mod debug_rpc;
 pub mod eth_rpc;
+pub mod kakarot_rpc;
 pub mod net_rpc;
 pub mod trace_rpc;
 pub mod txpool_rpc;
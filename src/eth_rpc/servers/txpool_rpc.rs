@@ -1,5 +1,6 @@
 use crate::eth_provider::provider::EthereumProvider;
 use crate::eth_rpc::api::txpool_api::TxPoolApiServer;
+use crate::models::txpool::NonceOrdered;
 use jsonrpsee::core::{async_trait, RpcResult as Result};
 use reth_primitives::Address;
 use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus};
@@ -25,7 +26,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> TxPoolApiServer for TxpoolRpc<
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
     ///
     /// Handler for `txpool_inspect`
-    async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
+    async fn txpool_inspect(&self) -> Result<NonceOrdered<TxpoolInspect>> {
         trace!(target: "rpc::eth", "Serving txpool_inspect");
 
         let mut inspect = TxpoolInspect::default();
@@ -44,7 +45,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> TxPoolApiServer for TxpoolRpc<
             );
         }
 
-        Ok(inspect)
+        Ok(NonceOrdered(inspect))
     }
 
     /// Returns the number of transactions currently pending for inclusion in the next block(s), as
@@ -63,9 +64,9 @@ impl<P: EthereumProvider + Send + Sync + 'static> TxPoolApiServer for TxpoolRpc<
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_contentFrom) for more details
     /// Handler for `txpool_contentFrom`
-    async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
+    async fn txpool_content_from(&self, from: Address) -> Result<NonceOrdered<TxpoolContentFrom>> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.eth_provider.txpool_content().await?.remove_from(&from))
+        Ok(NonceOrdered(self.eth_provider.txpool_content().await?.remove_from(&from)))
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -73,8 +74,8 @@ impl<P: EthereumProvider + Send + Sync + 'static> TxPoolApiServer for TxpoolRpc<
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> Result<TxpoolContent> {
+    async fn txpool_content(&self) -> Result<NonceOrdered<TxpoolContent>> {
         trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.eth_provider.txpool_content().await?)
+        Ok(NonceOrdered(self.eth_provider.txpool_content().await?))
     }
 }
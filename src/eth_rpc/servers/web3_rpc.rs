@@ -1,6 +1,7 @@
 use jsonrpsee::core::{async_trait, RpcResult as Result};
-use reth_primitives::{keccak256, Bytes, B256};
+use reth_primitives::{Bytes, B256};
 
+use crate::eth_provider::utils::keccak::keccak256;
 use crate::eth_rpc::api::web3_api::Web3ApiServer;
 
 /// The RPC module for the implementing Web3 Api { i.e rpc endpoints prefixed with web3_ }
@@ -4,15 +4,20 @@ use std::sync::Arc;
 use dotenvy::dotenv;
 use eyre::Result;
 use kakarot_rpc::config::{JsonRpcClientBuilder, KakarotRpcConfig, Network, SequencerGatewayProviderBuilder};
+use kakarot_rpc::eth_provider::circuit_breaker::{CircuitBreaker, CircuitBreakerProvider};
+use kakarot_rpc::eth_provider::config::KakarotConfig;
+use kakarot_rpc::eth_provider::constant::WARM_UP;
 use kakarot_rpc::eth_provider::database::Database;
 use kakarot_rpc::eth_provider::pending_pool::start_retry_service;
 use kakarot_rpc::eth_provider::provider::EthDataProvider;
 use kakarot_rpc::eth_rpc::config::RPCConfig;
+use kakarot_rpc::eth_rpc::ipc::run_ipc_server;
 use kakarot_rpc::eth_rpc::rpc::KakarotRpcModuleBuilder;
-use kakarot_rpc::eth_rpc::run_server;
+use kakarot_rpc::eth_rpc::{run_server, shutdown_server};
 use mongodb::options::{DatabaseOptions, ReadConcern, WriteConcern};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, SequencerGatewayProvider};
+use tokio::signal::unix::{signal, SignalKind};
 use tracing_subscriber::{filter, util::SubscriberInitExt};
 
 enum StarknetProvider {
@@ -29,8 +34,12 @@ async fn main() -> Result<()> {
     tracing_subscriber::FmtSubscriber::builder().with_env_filter(filter).finish().try_init()?;
 
     let starknet_config = KakarotRpcConfig::from_env()?;
+    starknet_config.validate()?;
 
     let rpc_config = RPCConfig::from_env()?;
+    let provider_config = KakarotConfig::from_env()?;
+    let circuit_breaker =
+        CircuitBreaker::new(provider_config.circuit_breaker_threshold, provider_config.circuit_breaker_cooldown);
 
     let starknet_provider = match &starknet_config.network {
         Network::Madara | Network::Katana | Network::Sharingan => {
@@ -73,28 +82,66 @@ async fn main() -> Result<()> {
         *nonce = deployer_nonce;
     }
 
-    let kakarot_rpc_module = match starknet_provider {
+    let (kakarot_rpc_module, retry_service) = match starknet_provider {
         StarknetProvider::JsonRpcClient(starknet_provider) => {
+            starknet_config.check_starknet_chain_id(&starknet_provider).await?;
             let starknet_provider = Arc::new(starknet_provider);
-            let eth_provider = EthDataProvider::new(db.clone(), starknet_provider).await?;
-            tokio::spawn(start_retry_service(eth_provider.clone()));
-            KakarotRpcModuleBuilder::new(eth_provider).rpc_module()?
+            let eth_provider = EthDataProvider::new(db.clone(), starknet_provider, provider_config).await?;
+            if let Some(class_hash) = starknet_config.kakarot_class_hash {
+                eth_provider.check_kakarot_class_hash(class_hash).await?;
+            }
+            if *WARM_UP {
+                eth_provider.warm_up().await?;
+            }
+            let retry_service = tokio::spawn(start_retry_service(eth_provider.clone()));
+            let eth_provider = CircuitBreakerProvider::new(eth_provider, circuit_breaker);
+            (KakarotRpcModuleBuilder::new(eth_provider).rpc_module()?, retry_service)
         }
         StarknetProvider::SequencerGatewayProvider(starknet_provider) => {
+            starknet_config.check_starknet_chain_id(&starknet_provider).await?;
             let starknet_provider = Arc::new(starknet_provider);
-            let eth_provider = EthDataProvider::new(db.clone(), starknet_provider).await?;
-            tokio::spawn(start_retry_service(eth_provider.clone()));
-            KakarotRpcModuleBuilder::new(eth_provider).rpc_module()?
+            let eth_provider = EthDataProvider::new(db.clone(), starknet_provider, provider_config).await?;
+            if let Some(class_hash) = starknet_config.kakarot_class_hash {
+                eth_provider.check_kakarot_class_hash(class_hash).await?;
+            }
+            if *WARM_UP {
+                eth_provider.warm_up().await?;
+            }
+            let retry_service = tokio::spawn(start_retry_service(eth_provider.clone()));
+            let eth_provider = CircuitBreakerProvider::new(eth_provider, circuit_breaker);
+            (KakarotRpcModuleBuilder::new(eth_provider).rpc_module()?, retry_service)
         }
     };
 
+    let ipc_server_handle = match &rpc_config.ipc_path {
+        Some(path) => {
+            let handle = run_ipc_server(kakarot_rpc_module.clone(), path, rpc_config.max_batch_size)?;
+            println!("IPC Server listening on {}...", handle.path().display());
+            Some(handle)
+        }
+        None => None,
+    };
+
+    let shutdown_grace_period = rpc_config.shutdown_grace_period;
     let (socket_addr, server_handle) = run_server(kakarot_rpc_module, rpc_config).await?;
 
     let url = format!("http://{socket_addr}");
 
     println!("RPC Server running on {url}...");
 
-    server_handle.stopped().await;
+    // Drain the in-flight requests on SIGTERM or Ctrl-C
+    let mut sigterm = signal(SignalKind::terminate())?;
+    tokio::select! {
+        _ = sigterm.recv() => tracing::info!("received SIGTERM, shutting down"),
+        _ = tokio::signal::ctrl_c() => tracing::info!("received SIGINT, shutting down"),
+        () = server_handle.clone().stopped() => {}
+    }
+    shutdown_server(server_handle, shutdown_grace_period).await;
+
+    if let Some(handle) = ipc_server_handle {
+        handle.stop(shutdown_grace_period).await;
+    }
+    retry_service.abort();
 
     Ok(())
 }
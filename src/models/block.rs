@@ -1,8 +1,75 @@
 use crate::eth_provider::constant::STARKNET_MODULUS;
+use crate::eth_provider::error::EthApiError;
+use crate::eth_provider::utils::keccak::keccak256;
 use crate::{eth_provider::error::EthereumDataFormatError, into_via_try_wrapper};
-use reth_primitives::{BlockId as EthereumBlockId, BlockNumberOrTag, U256};
+use alloy_rlp::Encodable;
+use reth_primitives::constants::EMPTY_ROOT_HASH;
+use reth_primitives::{BlockId as EthereumBlockId, BlockNumberOrTag, B256, U256};
+use reth_rpc_types::{Block, BlockTransactions, Header, RichBlock};
 use starknet::core::types::{BlockId as StarknetBlockId, BlockTag};
 
+/// Assembles the RPC block from its header and transactions.
+pub fn rpc_block(mut header: Header, transactions: BlockTransactions) -> Result<RichBlock, EthApiError> {
+    // The withdrawals are not supported, hence the withdrawals_root should always be empty.
+    if let Some(withdrawals_root) = header.withdrawals_root {
+        if withdrawals_root != EMPTY_ROOT_HASH {
+            return Err(EthApiError::Unsupported("withdrawals"));
+        }
+    }
+
+    // A block without transactions has empty transactions and receipts tries, whatever the indexer stored.
+    let is_empty = match &transactions {
+        BlockTransactions::Full(transactions) => transactions.is_empty(),
+        BlockTransactions::Hashes(hashes) => hashes.is_empty(),
+        _ => false,
+    };
+    if is_empty {
+        header.transactions_root = EMPTY_ROOT_HASH;
+        header.receipts_root = EMPTY_ROOT_HASH;
+    }
+
+    // A block can be numbered before being sealed, in which case it is stored with a zero hash.
+    header.hash = header.hash.filter(|hash| !hash.is_zero());
+
+    // This is how reth computes the block size.
+    // `https://github.com/paradigmxyz/reth/blob/v0.2.0-beta.5/crates/rpc/rpc-types-compat/src/block.rs#L66`
+    // The conversion requires a block number, which is missing for a block not numbered yet.
+    let size = reth_primitives::Header::try_from(Header { number: header.number.or(Some(0)), ..header.clone() })
+        .map_err(|_| EthereumDataFormatError::PrimitiveError)?
+        .length();
+    Ok(Block {
+        header,
+        transactions,
+        uncles: Default::default(),
+        size: Some(U256::from(size)),
+        withdrawals: Some(Default::default()),
+        other: Default::default(),
+    }
+    .into())
+}
+
+/// Hash returned for the pending block, which is not sealed yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PendingBlockHash {
+    /// The hash is null.
+    #[default]
+    Null,
+    /// The hash is a placeholder derived from the parent hash and the timestamp of the block.
+    Placeholder,
+}
+
+impl PendingBlockHash {
+    /// Returns the hash to expose for the given pending header.
+    pub fn hash(self, header: &Header) -> Option<B256> {
+        match self {
+            Self::Null => None,
+            Self::Placeholder => {
+                Some(keccak256([header.parent_hash.as_slice(), &header.timestamp.to_be_bytes()].concat()))
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct EthBlockId(EthereumBlockId);
 
@@ -65,3 +132,130 @@ impl From<EthBlockNumberOrTag> for StarknetBlockId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use starknet_crypto::FieldElement;
+
+    fn block_number_or_tag() -> impl Strategy<Value = BlockNumberOrTag> {
+        prop_oneof![
+            any::<u64>().prop_map(BlockNumberOrTag::Number),
+            Just(BlockNumberOrTag::Latest),
+            Just(BlockNumberOrTag::Pending),
+            Just(BlockNumberOrTag::Safe),
+            Just(BlockNumberOrTag::Finalized),
+            Just(BlockNumberOrTag::Earliest),
+        ]
+    }
+
+    fn block_hash() -> impl Strategy<Value = B256> {
+        prop_oneof![
+            any::<[u8; 32]>().prop_map(B256::from),
+            // Hashes at or just above the Starknet modulus, which must wrap around.
+            any::<u64>()
+                .prop_map(|offset| B256::from(STARKNET_MODULUS.wrapping_add(U256::from(offset)).to_be_bytes::<32>())),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_block_number_or_tag_to_starknet_block_id(block_number_or_tag in block_number_or_tag()) {
+            // When
+            let block_id = StarknetBlockId::try_from(EthBlockId::new(block_number_or_tag.into())).unwrap();
+
+            // Then
+            let expected = match block_number_or_tag {
+                BlockNumberOrTag::Number(number) => StarknetBlockId::Number(number),
+                BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => StarknetBlockId::Tag(BlockTag::Pending),
+                BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized => StarknetBlockId::Tag(BlockTag::Latest),
+                BlockNumberOrTag::Earliest => StarknetBlockId::Number(0),
+            };
+            prop_assert_eq!(block_id, expected);
+        }
+
+        #[test]
+        fn test_block_hash_to_starknet_block_id(hash in block_hash()) {
+            // When
+            let block_id = StarknetBlockId::try_from(EthBlockId::new(hash.into())).unwrap();
+
+            // Then
+            let value = U256::from_be_slice(hash.as_slice());
+            let expected = FieldElement::from_bytes_be(&value.wrapping_rem(STARKNET_MODULUS).to_be_bytes::<32>()).unwrap();
+            prop_assert_eq!(block_id, StarknetBlockId::Hash(expected));
+            if value < STARKNET_MODULUS {
+                prop_assert_eq!(expected.to_bytes_be(), hash.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pending_block_hash_null() {
+        // Given
+        let header = Header { parent_hash: B256::repeat_byte(1), timestamp: 1_700_000_000, ..Default::default() };
+
+        // When
+        let hash = PendingBlockHash::Null.hash(&header);
+
+        // Then
+        assert!(hash.is_none());
+    }
+
+    #[test]
+    fn test_pending_block_hash_placeholder() {
+        // Given
+        let header = Header { parent_hash: B256::repeat_byte(1), timestamp: 1_700_000_000, ..Default::default() };
+        let later = Header { timestamp: header.timestamp + 1, ..header.clone() };
+
+        // When
+        let hash = PendingBlockHash::Placeholder.hash(&header);
+
+        // Then
+        assert!(hash.is_some_and(|hash| !hash.is_zero()));
+        assert_eq!(hash, PendingBlockHash::Placeholder.hash(&header));
+        assert_ne!(hash, PendingBlockHash::Placeholder.hash(&later));
+    }
+
+    #[test]
+    fn test_rpc_block_without_transactions() {
+        // Given
+        let header = Header {
+            number: Some(1),
+            transactions_root: B256::repeat_byte(1),
+            receipts_root: B256::repeat_byte(2),
+            ..Default::default()
+        };
+
+        for transactions in [BlockTransactions::Full(vec![]), BlockTransactions::Hashes(vec![])] {
+            // When
+            let block = rpc_block(header.clone(), transactions).unwrap();
+
+            // Then
+            assert_eq!(block.header.transactions_root, EMPTY_ROOT_HASH);
+            assert_eq!(block.header.receipts_root, EMPTY_ROOT_HASH);
+            let transactions = serde_json::to_value(&block.transactions).unwrap();
+            assert_eq!(transactions, serde_json::json!([]));
+        }
+    }
+
+    #[test]
+    fn test_rpc_block_hash_and_number() {
+        let hash = B256::repeat_byte(1);
+        for (hash, number) in
+            [(Some(hash), Some(1)), (Some(hash), None), (None, Some(1)), (None, None), (Some(B256::ZERO), Some(1))]
+        {
+            // Given
+            let header = Header { hash, number, ..Default::default() };
+
+            // When
+            let block = rpc_block(header, BlockTransactions::Hashes(vec![])).unwrap();
+
+            // Then
+            // A zero hash is the hash of a block which isn't sealed yet
+            assert_eq!(block.header.hash, hash.filter(|hash| !hash.is_zero()));
+            assert_eq!(block.header.number, number);
+            assert!(block.size.is_some());
+        }
+    }
+}
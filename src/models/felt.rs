@@ -55,11 +55,19 @@ impl TryFrom<Felt252Wrapper> for Address {
     }
 }
 
+impl TryFrom<Felt252Wrapper> for u64 {
+    type Error = EthereumDataFormatError;
+
+    fn try_from(felt: Felt252Wrapper) -> Result<Self, Self::Error> {
+        Self::try_from(felt.0).map_err(|_| EthereumDataFormatError::PrimitiveError)
+    }
+}
+
 impl TryFrom<B256> for Felt252Wrapper {
     type Error = EthereumDataFormatError;
 
     fn try_from(value: B256) -> Result<Self, Self::Error> {
-        Ok(Self(FieldElement::from_bytes_be(value.as_ref()).map_err(|_| EthereumDataFormatError::PrimitiveError)?))
+        Ok(Self(FieldElement::from_bytes_be(value.as_ref()).map_err(|_| EthereumDataFormatError::FeltOverflow)?))
     }
 }
 
@@ -67,7 +75,7 @@ impl TryFrom<U256> for Felt252Wrapper {
     type Error = EthereumDataFormatError;
 
     fn try_from(u256: U256) -> Result<Self, Self::Error> {
-        Ok(Self(FieldElement::from_bytes_be(&u256.to_be_bytes()).map_err(|_| EthereumDataFormatError::PrimitiveError)?))
+        Ok(Self(FieldElement::from_bytes_be(&u256.to_be_bytes()).map_err(|_| EthereumDataFormatError::FeltOverflow)?))
     }
 }
 
@@ -123,6 +131,7 @@ mod tests {
     use hex::FromHex;
 
     use super::*;
+    use crate::eth_provider::error::{EthApiError, EthRpcErrorCode};
 
     // 2**160 - 1
     const MAX_ADDRESS: &str = "ffffffffffffffffffffffffffffffffffffffff";
@@ -155,6 +164,21 @@ mod tests {
         Address::try_from(address).unwrap();
     }
 
+    #[test]
+    fn test_u64_try_from_felt() {
+        // Given
+        let block_number: Felt252Wrapper = FieldElement::from(u64::MAX).into();
+        let oversized_block_number: Felt252Wrapper = (FieldElement::from(u64::MAX) + FieldElement::ONE).into();
+
+        // When
+        let block_number = u64::try_from(block_number);
+        let oversized_block_number = u64::try_from(oversized_block_number);
+
+        // Then
+        assert_eq!(block_number.unwrap(), u64::MAX);
+        assert!(matches!(oversized_block_number, Err(EthereumDataFormatError::PrimitiveError)));
+    }
+
     #[test]
     fn test_felt_try_from_b256_should_pass() {
         // Given
@@ -169,7 +193,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "PrimitiveError")]
+    #[should_panic(expected = "FeltOverflow")]
     fn test_felt_try_from_b256_should_fail() {
         // Given
         let hash = B256::from_str(OVERFLOW_FELT).unwrap();
@@ -192,7 +216,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "PrimitiveError")]
+    #[should_panic(expected = "FeltOverflow")]
     fn test_felt_try_from_u256_should_fail() {
         // Given
         let hash = U256::from_str_radix(OVERFLOW_FELT, 16).unwrap();
@@ -200,4 +224,17 @@ mod tests {
         // When
         Felt252Wrapper::try_from(hash).unwrap();
     }
+
+    #[test]
+    fn test_felt_try_from_b256_overflow_error() {
+        // Given
+        let hash = B256::from_str(OVERFLOW_FELT).unwrap();
+
+        // When
+        let err = EthApiError::from(Felt252Wrapper::try_from(hash).unwrap_err());
+
+        // Then
+        assert_eq!(err.to_string(), "ethereum data format error: value exceeds the Starknet field prime");
+        assert_eq!(EthRpcErrorCode::from(err), EthRpcErrorCode::InvalidParams);
+    }
 }
@@ -0,0 +1,27 @@
+use reth_primitives::U64;
+use reth_rpc_types::Log;
+use serde::{Deserialize, Serialize};
+
+/// Position of a log in the chain, used to resume a paginated logs query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCursor {
+    pub block_number: U64,
+    pub log_index: U64,
+}
+
+impl LogCursor {
+    /// Returns the cursor of the given log, if it is part of a block.
+    pub fn from_log(log: &Log) -> Option<Self> {
+        Some(Self { block_number: U64::from(log.block_number?), log_index: U64::from(log.log_index?) })
+    }
+}
+
+/// A page of logs, along with the cursor to pass in order to fetch the next page.
+/// The cursor is `None` once all the logs matching the query have been returned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedLogs {
+    pub logs: Vec<Log>,
+    pub cursor: Option<LogCursor>,
+}
@@ -1,4 +1,7 @@
 pub mod balance;
 pub mod block;
 pub mod felt;
+pub mod log;
+pub mod receipt;
 pub mod transaction;
+pub mod txpool;
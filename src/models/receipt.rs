@@ -0,0 +1,167 @@
+use reth_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{
+    Event, ExecutionResult, MaybePendingTransactionReceipt, PendingTransactionReceipt, TransactionReceipt,
+};
+use starknet_crypto::FieldElement;
+
+use crate::eth_provider::gas::starknet_fee_to_gas;
+use crate::eth_provider::starknet::kakarot_core::EVM_CONTRACT_DEPLOYED;
+use crate::into_via_wrapper;
+use crate::models::felt::Felt252Wrapper;
+
+/// Returns the Ethereum receipt status of the Starknet execution result:
+/// `true` (status 1) if the execution succeeded, `false` (status 0) if it was reverted.
+pub const fn execution_result_to_status(execution_result: &ExecutionResult) -> bool {
+    matches!(execution_result, ExecutionResult::Succeeded)
+}
+
+/// A transaction receipt with the non-standard debug fields of Kakarot, which are only
+/// serialized when set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KakarotTransactionReceipt {
+    #[serde(flatten)]
+    pub receipt: reth_rpc_types::TransactionReceipt,
+    /// Hash of the Starknet transaction carrying the Ethereum transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starknet_transaction_hash: Option<B256>,
+}
+
+impl From<reth_rpc_types::TransactionReceipt> for KakarotTransactionReceipt {
+    fn from(receipt: reth_rpc_types::TransactionReceipt) -> Self {
+        Self { receipt, starknet_transaction_hash: None }
+    }
+}
+
+/// Returns the actual fee paid by the Starknet invoke transaction of the receipt.
+pub const fn invoke_actual_fee(receipt: &MaybePendingTransactionReceipt) -> Option<FieldElement> {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(receipt)) => Some(receipt.actual_fee.amount),
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(receipt)) => {
+            Some(receipt.actual_fee.amount)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the events emitted by the Starknet invoke transaction of the receipt.
+pub fn invoke_events(receipt: &MaybePendingTransactionReceipt) -> &[Event] {
+    match receipt {
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(receipt)) => &receipt.events,
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(receipt)) => &receipt.events,
+        _ => &[],
+    }
+}
+
+/// Returns the EVM address of the first contract deployed by a transaction, read from the
+/// `evm_contract_deployed` events emitted by Kakarot. The event carries the deployed address
+/// whatever the scheme, so it is the CREATE or the CREATE2 address of the contract.
+pub fn deployed_contract_address(events: &[Event], kakarot_address: FieldElement) -> Option<Address> {
+    events
+        .iter()
+        .find(|event| event.from_address == kakarot_address && event.keys.first() == Some(&*EVM_CONTRACT_DEPLOYED))
+        .and_then(|event| event.data.first())
+        .and_then(|address| Address::try_from(Felt252Wrapper::from(*address)).ok())
+}
+
+/// Approximates the gas used by an EVM transaction from the actual fee of the Starknet
+/// transaction carrying it, divided by its effective gas price. Returns zero for a zero
+/// gas price.
+pub fn approximate_gas_used(actual_fee: FieldElement, effective_gas_price: u128, starknet_fee_ratio: u64) -> u128 {
+    let actual_fee: U256 = into_via_wrapper!(actual_fee);
+    starknet_fee_to_gas(actual_fee, U256::from(effective_gas_price), starknet_fee_ratio).try_into().unwrap_or(u128::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_succeeded_receipt_status() {
+        // Given
+        let execution_result = ExecutionResult::Succeeded;
+
+        // When
+        let status = execution_result_to_status(&execution_result);
+
+        // Then
+        assert!(status);
+    }
+
+    #[test]
+    fn test_reverted_receipt_status() {
+        // Given
+        let execution_result = ExecutionResult::Reverted { reason: "Kakarot: eth_send_transaction failed".to_string() };
+
+        // When
+        let status = execution_result_to_status(&execution_result);
+
+        // Then
+        assert!(!status);
+    }
+
+    fn deployment_event(kakarot_address: FieldElement, address: Address) -> Event {
+        Event {
+            from_address: kakarot_address,
+            keys: vec![*EVM_CONTRACT_DEPLOYED],
+            data: vec![Felt252Wrapper::from(address).into(), FieldElement::from(0x1234_u64)],
+        }
+    }
+
+    #[test]
+    fn test_deployed_contract_address_create() {
+        // Given
+        // Anvil first account
+        let kakarot_address = FieldElement::from(0x4b_u64);
+        let from = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let transfer = Event { keys: vec![FieldElement::from(0x42_u64)], ..deployment_event(kakarot_address, from) };
+        let events = vec![
+            transfer,
+            deployment_event(kakarot_address, from.create(0)),
+            deployment_event(kakarot_address, from.create(1)),
+        ];
+
+        // When
+        let address = deployed_contract_address(&events, kakarot_address);
+
+        // Then
+        assert_eq!(address, Some(Address::from_str("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap()));
+        assert_eq!(deployed_contract_address(&events[..1], kakarot_address), None);
+    }
+
+    #[test]
+    fn test_deployed_contract_address_create2() {
+        // Given
+        // Example 5 of EIP-1014
+        let factory = Address::from_str("0x00000000000000000000000000000000deadbeef").unwrap();
+        let salt = B256::from_str("0x00000000000000000000000000000000000000000000000000000000cafebabe").unwrap();
+        let kakarot_address = FieldElement::from(0x4b_u64);
+        let deployed = factory.create2_from_code(salt, [0xde, 0xad, 0xbe, 0xef]);
+        let events = vec![deployment_event(kakarot_address, deployed)];
+
+        // When
+        let address = deployed_contract_address(&events, kakarot_address);
+
+        // Then
+        assert_eq!(address, Some(Address::from_str("0x60f3f640a8508fC6a86d45DF051962668E1e8AC7").unwrap()));
+        assert_eq!(deployed_contract_address(&events, FieldElement::ZERO), None);
+    }
+
+    #[test]
+    fn test_approximate_gas_used() {
+        // Given
+        // A transfer of 21,000 gas at 10 wei per gas, paid 1,000 Starknet fee units per wei
+        let actual_fee = FieldElement::from(210_000_000_u64);
+        let effective_gas_price = 10;
+        let starknet_fee_ratio = 1_000;
+
+        // When
+        let gas_used = approximate_gas_used(actual_fee, effective_gas_price, starknet_fee_ratio);
+
+        // Then
+        assert_eq!(gas_used, 21_000);
+        assert_eq!(approximate_gas_used(actual_fee, 0, starknet_fee_ratio), 0);
+    }
+}
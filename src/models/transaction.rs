@@ -1,10 +1,806 @@
+use alloy_rlp::{Decodable, Header};
+use reth_primitives::{
+    AccessList, Address, Bytes, Signature, Transaction, TransactionSigned, TransactionSignedEcRecovered, TxEip1559,
+    TxEip2930, TxKind, TxLegacy, B256, U256,
+};
+use reth_rpc_types_compat::transaction::from_recovered;
+use starknet::core::types::{InvokeTransaction, L1HandlerTransaction, Transaction as StarknetTransaction};
+use starknet_crypto::FieldElement;
+
+use crate::eth_provider::error::{EthApiError, EthereumDataFormatError, SignatureError};
+use crate::eth_provider::starknet::kakarot_core::starknet_address;
+use crate::into_via_wrapper;
+use crate::models::felt::Felt252Wrapper;
+
+/// Returns true if the Starknet transaction is an EVM transaction sent to Kakarot.
+/// The calldata of such a transaction has the following layout:
+/// - call array length <- calldata[0]
+/// - to <- calldata[1]
+/// - selector <- calldata[2]
+/// - data offset <- calldata[3]
+/// - data length <- calldata[4]
+/// - calldata length <- calldata[5]
+/// - RLP encoded unsigned EVM transaction <- calldata[6..]
+pub fn is_kakarot_transaction(tx: &StarknetTransaction, kakarot_address: FieldElement) -> bool {
+    let calldata = match tx {
+        StarknetTransaction::Invoke(InvokeTransaction::V1(tx)) => &tx.calldata,
+        StarknetTransaction::Invoke(InvokeTransaction::V3(tx)) => &tx.calldata,
+        StarknetTransaction::Invoke(InvokeTransaction::V0(_))
+        | StarknetTransaction::L1Handler(_)
+        | StarknetTransaction::Declare(_)
+        | StarknetTransaction::Deploy(_)
+        | StarknetTransaction::DeployAccount(_) => return false,
+    };
+    calldata.get(1).is_some_and(|to| *to == kakarot_address)
+}
+
+/// Number of felts preceding the RLP encoded EVM transaction in the Kakarot calldata.
+const KAKAROT_CALLDATA_PREFIX_LEN: usize = 6;
+
+/// The EVM call extracted from the calldata of a Kakarot invoke transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmCall {
+    /// The destination of the call, or [`TxKind::Create`] for a deployment.
+    pub to: TxKind,
+    /// The value sent with the call.
+    pub value: U256,
+    /// The input data of the call.
+    pub input: Bytes,
+}
+
+impl From<&Transaction> for EvmCall {
+    fn from(transaction: &Transaction) -> Self {
+        let to = transaction.to().map_or(TxKind::Create, TxKind::Call);
+        Self { to, value: transaction.value(), input: transaction.input().clone() }
+    }
+}
+
+/// Decodes the EVM call contained in the calldata of a Kakarot invoke transaction.
+/// See [`is_kakarot_transaction`] for the layout of the calldata.
+pub fn decode_kakarot_calldata(felts: &[FieldElement]) -> Result<EvmCall, EthereumDataFormatError> {
+    Ok(EvmCall::from(&decode_kakarot_transaction(felts)?))
+}
+
+/// Decodes the unsigned EVM transaction contained in the calldata of a Kakarot invoke transaction.
+pub fn decode_kakarot_transaction(felts: &[FieldElement]) -> Result<Transaction, EthereumDataFormatError> {
+    if felts.len() < KAKAROT_CALLDATA_PREFIX_LEN {
+        return Err(EthereumDataFormatError::TransactionConversionError);
+    }
+    let (prefix, data) = felts.split_at(KAKAROT_CALLDATA_PREFIX_LEN);
+
+    // The calldata length should match the number of felts left
+    let calldata_len = u64::try_from(prefix[5])
+        .ok()
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or(EthereumDataFormatError::TransactionConversionError)?;
+    if calldata_len != data.len() {
+        return Err(EthereumDataFormatError::TransactionConversionError);
+    }
+
+    // Each felt of the data holds a single byte
+    let bytes = data
+        .iter()
+        .map(|felt| u8::try_from(*felt))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| EthereumDataFormatError::TransactionConversionError)?;
+
+    decode_unsigned_transaction(&bytes).map_err(|_| EthereumDataFormatError::TransactionConversionError)
+}
+
+/// Decodes an EVM transaction encoded without its signature, i.e. the payload
+/// which is signed by the sender of the transaction.
+fn decode_unsigned_transaction(bytes: &[u8]) -> alloy_rlp::Result<Transaction> {
+    let mut buf = bytes;
+
+    // Typed transactions are prefixed by their type, as per EIP-2718
+    let tx_type = match buf.first() {
+        Some(&tx_type) if tx_type <= 0x7f => {
+            buf = &buf[1..];
+            Some(tx_type)
+        }
+        _ => None,
+    };
+
+    let header = Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString);
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort);
+    }
+    let payload = &mut &buf[..header.payload_length];
+
+    let transaction = match tx_type {
+        None => {
+            let mut tx = TxLegacy {
+                nonce: Decodable::decode(payload)?,
+                gas_price: Decodable::decode(payload)?,
+                gas_limit: Decodable::decode(payload)?,
+                to: Decodable::decode(payload)?,
+                value: Decodable::decode(payload)?,
+                input: Decodable::decode(payload)?,
+                chain_id: None,
+            };
+            // EIP-155 transactions append [chain_id, 0, 0] to the payload
+            if !payload.is_empty() {
+                tx.chain_id = Some(Decodable::decode(payload)?);
+                let _: u8 = Decodable::decode(payload)?;
+                let _: u8 = Decodable::decode(payload)?;
+            }
+            Transaction::Legacy(tx)
+        }
+        Some(1) => Transaction::Eip2930(TxEip2930 {
+            chain_id: Decodable::decode(payload)?,
+            nonce: Decodable::decode(payload)?,
+            gas_price: Decodable::decode(payload)?,
+            gas_limit: Decodable::decode(payload)?,
+            to: Decodable::decode(payload)?,
+            value: Decodable::decode(payload)?,
+            input: Decodable::decode(payload)?,
+            access_list: AccessList::decode(payload)?,
+        }),
+        Some(2) => Transaction::Eip1559(TxEip1559 {
+            chain_id: Decodable::decode(payload)?,
+            nonce: Decodable::decode(payload)?,
+            max_priority_fee_per_gas: Decodable::decode(payload)?,
+            max_fee_per_gas: Decodable::decode(payload)?,
+            gas_limit: Decodable::decode(payload)?,
+            to: Decodable::decode(payload)?,
+            value: Decodable::decode(payload)?,
+            input: Decodable::decode(payload)?,
+            access_list: AccessList::decode(payload)?,
+        }),
+        Some(_) => return Err(alloy_rlp::Error::Custom("unsupported transaction type")),
+    };
+
+    if !payload.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength);
+    }
+
+    Ok(transaction)
+}
+
+/// Rebuilds the EVM signature of a transaction from the signature of the Kakarot invoke
+/// transaction, which has the following layout: [r.low, r.high, s.low, s.high, v].
+/// In case of a Legacy Transaction, v := {0, 1} + chain_id * 2 + 35 or {0, 1} + 27
+/// for pre EIP-155 transactions. Else, v is the y parity.
+pub fn signature_from_felts(signature: &[FieldElement], transaction: &Transaction) -> Result<Signature, EthApiError> {
+    let [r_low, r_high, s_low, s_high, v] = signature else {
+        return Err(SignatureError::MissingSignature.into());
+    };
+
+    let into_u256 = |low: FieldElement, high: FieldElement| -> Result<U256, EthApiError> {
+        let low: U256 = into_via_wrapper!(low);
+        let high: U256 = into_via_wrapper!(high);
+        if low > U256::from(u128::MAX) || high > U256::from(u128::MAX) {
+            return Err(EthereumDataFormatError::PrimitiveError.into());
+        }
+        Ok(low + (high << 128))
+    };
+    let r = into_u256(*r_low, *r_high)?;
+    let s = into_u256(*s_low, *s_high)?;
+
+    let v = u64::try_from(*v).map_err(|_| SignatureError::InvalidParity)?;
+    let y_parity = match transaction {
+        Transaction::Legacy(tx) => match tx.chain_id {
+            Some(chain_id) => chain_id.checked_mul(2).and_then(|c| c.checked_add(35)).and_then(|c| v.checked_sub(c)),
+            None => v.checked_sub(27),
+        },
+        _ => Some(v),
+    };
+    let odd_y_parity = match y_parity {
+        Some(0) => false,
+        Some(1) => true,
+        _ => return Err(SignatureError::InvalidParity.into()),
+    };
+
+    Ok(Signature { r, s, odd_y_parity })
+}
+
+/// Converts a Kakarot invoke transaction into the EVM transaction it carries. The
+/// signature is rebuilt from the Starknet signature and the sender is recovered from it.
+/// The Starknet address of the recovered sender is checked against the sender of the
+/// invoke transaction: a mismatch is logged, or returned as an error if
+/// `strict_signature_checks` is set.
+pub fn starknet_to_rpc_transaction(
+    transaction: &StarknetTransaction,
+    strict_signature_checks: bool,
+) -> Result<reth_rpc_types::Transaction, EthApiError> {
+    let (sender_address, calldata, signature) = match transaction {
+        StarknetTransaction::Invoke(InvokeTransaction::V1(tx)) => (tx.sender_address, &tx.calldata, &tx.signature),
+        StarknetTransaction::Invoke(InvokeTransaction::V3(tx)) => (tx.sender_address, &tx.calldata, &tx.signature),
+        StarknetTransaction::Invoke(InvokeTransaction::V0(_))
+        | StarknetTransaction::L1Handler(_)
+        | StarknetTransaction::Declare(_)
+        | StarknetTransaction::Deploy(_)
+        | StarknetTransaction::DeployAccount(_) => {
+            return Err(EthereumDataFormatError::TransactionConversionError.into())
+        }
+    };
+
+    let evm_transaction = decode_kakarot_transaction(calldata)?;
+    let signature = signature_from_felts(signature, &evm_transaction)?;
+    let transaction_signed = TransactionSigned::from_transaction_and_signature(evm_transaction, signature);
+    let signer = transaction_signed.recover_signer().ok_or(SignatureError::RecoveryError)?;
+
+    if starknet_address(signer) != sender_address {
+        if strict_signature_checks {
+            return Err(SignatureError::SignerMismatch.into());
+        }
+        tracing::warn!(
+            "Recovered signer {signer} does not match sender {sender_address:#x} of transaction {:#x}",
+            transaction.transaction_hash()
+        );
+    }
+
+    Ok(from_recovered(TransactionSignedEcRecovered::from_signed_transaction(transaction_signed, signer)))
+}
+
+/// Number of felts preceding the data in the calldata of a Kakarot L1 handler transaction.
+const L1_HANDLER_CALLDATA_PREFIX_LEN: usize = 5;
+
+/// Converts a L1 handler transaction sent to Kakarot into a synthetic EVM transaction,
+/// sent from the L1 address which emitted the message, e.g. the bridge. The calldata of
+/// such a transaction has the following layout:
+/// - L1 from address <- calldata[0]
+/// - L1 sender <- calldata[1]
+/// - to <- calldata[2]
+/// - value <- calldata[3]
+/// - data length <- calldata[4]
+/// - data <- calldata[5..]
+pub fn l1_handler_to_rpc_transaction(
+    transaction: &L1HandlerTransaction,
+) -> Result<reth_rpc_types::Transaction, EthereumDataFormatError> {
+    let calldata = &transaction.calldata;
+    if calldata.len() < L1_HANDLER_CALLDATA_PREFIX_LEN {
+        return Err(EthereumDataFormatError::TransactionConversionError);
+    }
+    let (prefix, data) = calldata.split_at(L1_HANDLER_CALLDATA_PREFIX_LEN);
+
+    let data_len = u64::try_from(prefix[4])
+        .ok()
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or(EthereumDataFormatError::TransactionConversionError)?;
+    if data_len != data.len() {
+        return Err(EthereumDataFormatError::TransactionConversionError);
+    }
+    let input = data
+        .iter()
+        .map(|felt| u8::try_from(*felt))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| EthereumDataFormatError::TransactionConversionError)?;
+
+    Ok(reth_rpc_types::Transaction {
+        hash: B256::from_slice(&transaction.transaction_hash.to_bytes_be()),
+        nonce: transaction.nonce,
+        from: Address::try_from(Felt252Wrapper::from(prefix[0]))?,
+        to: Some(Address::try_from(Felt252Wrapper::from(prefix[2]))?),
+        value: into_via_wrapper!(prefix[3]),
+        input: Bytes::from(input),
+        transaction_type: Some(0),
+        ..Default::default()
+    })
+}
+
+/// Converts the transactions of a Starknet block into the EVM transactions they carry.
+/// Transactions which are not sent to Kakarot, such as declare or deploy account
+/// transactions, are skipped, as are the Kakarot transactions that fail to convert. The
+/// reverted transactions are kept: a reverted execution is still a mined EVM transaction.
+/// If `include_l1_handlers` is set, the L1 handler transactions sent to Kakarot, i.e. the
+/// deposits, are surfaced as synthetic EVM transactions, see [`l1_handler_to_rpc_transaction`].
+pub fn filter_starknet_into_eth_txs(
+    transactions: &[StarknetTransaction],
+    kakarot_address: FieldElement,
+    strict_signature_checks: bool,
+    include_l1_handlers: bool,
+) -> Vec<reth_rpc_types::Transaction> {
+    // Most transactions of a block are Kakarot transactions, reserve for all of them upfront
+    let mut rpc_transactions = Vec::with_capacity(transactions.len());
+    for transaction in transactions {
+        let rpc_transaction = match transaction {
+            StarknetTransaction::L1Handler(tx) if include_l1_handlers && tx.contract_address == kakarot_address => {
+                l1_handler_to_rpc_transaction(tx).map_err(EthApiError::from)
+            }
+            _ if is_kakarot_transaction(transaction, kakarot_address) => {
+                starknet_to_rpc_transaction(transaction, strict_signature_checks)
+            }
+            _ => continue,
+        };
+        match rpc_transaction {
+            Ok(rpc_transaction) => rpc_transactions.push(rpc_transaction),
+            Err(err) => tracing::warn!("Failed to convert transaction {:#x}: {err:?}", transaction.transaction_hash()),
+        }
+    }
+    rpc_transactions
+}
+
+/// Returns the EIP-2718 binary encoding of the given RPC transaction.
+pub fn rpc_transaction_to_raw(transaction: reth_rpc_types::Transaction) -> Result<Bytes, EthApiError> {
+    let transaction = TransactionSignedEcRecovered::try_from(transaction)
+        .map_err(|_| EthereumDataFormatError::TransactionConversionError)?;
+    Ok(transaction.into_signed().envelope_encoded())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::eth_provider::error::EthereumDataFormatError;
-    use reth_primitives::{Address, Bytes, Signature, Transaction, TransactionSignedEcRecovered, TxType, U256};
+    use super::*;
+    use crate::eth_provider::utils::split_u256;
+    use reth_primitives::{sign_message, TxType};
     use reth_rpc_types::{AccessList, AccessListItem};
+    use serde_json::json;
+    use starknet::core::types::{DataAvailabilityMode, InvokeTransactionV3, ResourceBounds, ResourceBoundsMapping};
     use std::str::FromStr;
 
+    fn starknet_transaction_fixture() -> StarknetTransaction {
+        serde_json::from_str(include_str!("test_data/conversion/starknet/transaction.json"))
+            .expect("Failed to deserialize Starknet transaction")
+    }
+
+    fn fixture_kakarot_address() -> FieldElement {
+        FieldElement::from_hex_be("0x2fcc35a38e2b535ccecf635e67fdb558cb24924e011a5b6851ec7e3f03f8473").unwrap()
+    }
+
+    #[test]
+    fn test_is_kakarot_transaction_invoke_to_kakarot() {
+        // Given
+        let tx = starknet_transaction_fixture();
+
+        // When
+        let is_kakarot_tx = is_kakarot_transaction(&tx, fixture_kakarot_address());
+
+        // Then
+        assert!(is_kakarot_tx);
+    }
+
+    #[test]
+    fn test_is_kakarot_transaction_invoke_elsewhere() {
+        // Given
+        let tx = starknet_transaction_fixture();
+
+        // When
+        let is_kakarot_tx = is_kakarot_transaction(&tx, FieldElement::from(0xdead_u64));
+
+        // Then
+        assert!(!is_kakarot_tx);
+    }
+
+    fn kakarot_calldata(transaction: &Transaction) -> Vec<FieldElement> {
+        let mut bytes = Vec::new();
+        transaction.encode_without_signature(&mut bytes);
+
+        let mut calldata = vec![
+            FieldElement::ONE,
+            fixture_kakarot_address(),
+            FieldElement::from(0x1234_u64),
+            FieldElement::ZERO,
+            bytes.len().into(),
+            bytes.len().into(),
+        ];
+        calldata.extend(bytes.into_iter().map(FieldElement::from));
+        calldata
+    }
+
+    #[test]
+    fn test_decode_kakarot_calldata_contract_call() {
+        // Given
+        let to = Address::from_str("0x0000000000000000000000000000000000000002").unwrap();
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce: 3,
+            gas_limit: 100_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            to: TxKind::Call(to),
+            value: U256::from(5),
+            input: Bytes::from_str("0xd09de08a").unwrap(),
+            ..Default::default()
+        });
+
+        // When
+        let evm_call = decode_kakarot_calldata(&kakarot_calldata(&transaction)).unwrap();
+
+        // Then
+        assert_eq!(
+            evm_call,
+            EvmCall { to: TxKind::Call(to), value: U256::from(5), input: Bytes::from_str("0xd09de08a").unwrap() }
+        );
+    }
+
+    #[test]
+    fn test_decode_kakarot_calldata_eth_transfer() {
+        // Given
+        let to = Address::from_str("0x0000000000000000000000000000000000000003").unwrap();
+        let transaction = Transaction::Legacy(TxLegacy {
+            chain_id: Some(1),
+            nonce: 0,
+            gas_price: 10,
+            gas_limit: 21_000,
+            to: TxKind::Call(to),
+            value: U256::from(1_000_000_000_u64),
+            input: Bytes::default(),
+        });
+
+        // When
+        let evm_call = decode_kakarot_calldata(&kakarot_calldata(&transaction)).unwrap();
+
+        // Then
+        assert_eq!(
+            evm_call,
+            EvmCall { to: TxKind::Call(to), value: U256::from(1_000_000_000_u64), input: Bytes::default() }
+        );
+    }
+
+    #[test]
+    fn test_decode_kakarot_calldata_deployment_fixture() {
+        // Given
+        let StarknetTransaction::Invoke(InvokeTransaction::V1(tx)) = starknet_transaction_fixture() else {
+            panic!("Expected invoke v1 transaction");
+        };
+
+        // When
+        let evm_call = decode_kakarot_calldata(&tx.calldata).unwrap();
+
+        // Then
+        assert_eq!(evm_call.to, TxKind::Create);
+        assert_eq!(evm_call.value, U256::ZERO);
+        assert_eq!(evm_call.input.len(), 0x160);
+    }
+
+    #[test]
+    fn test_decode_kakarot_calldata_malformed() {
+        // Given
+        let transaction = Transaction::Legacy(TxLegacy { chain_id: Some(1), ..Default::default() });
+        let calldata = kakarot_calldata(&transaction);
+
+        let mut wrong_len = calldata.clone();
+        wrong_len.pop();
+        let mut not_a_byte = calldata.clone();
+        not_a_byte[6] = FieldElement::from(0x100_u64);
+
+        // When
+        let short = decode_kakarot_calldata(&calldata[..5]);
+        let wrong_len = decode_kakarot_calldata(&wrong_len);
+        let not_a_byte = decode_kakarot_calldata(&not_a_byte);
+
+        // Then
+        assert!(matches!(short, Err(EthereumDataFormatError::TransactionConversionError)));
+        assert!(matches!(wrong_len, Err(EthereumDataFormatError::TransactionConversionError)));
+        assert!(matches!(not_a_byte, Err(EthereumDataFormatError::TransactionConversionError)));
+    }
+
+    fn kakarot_signature(signature: &Signature, transaction: &Transaction) -> Vec<FieldElement> {
+        let mut felts = Vec::with_capacity(5);
+        felts.extend_from_slice(&split_u256::<FieldElement>(signature.r));
+        felts.extend_from_slice(&split_u256::<FieldElement>(signature.s));
+        if let Transaction::Legacy(tx) = transaction {
+            felts.push(signature.v(tx.chain_id).into());
+        } else {
+            felts.push(u64::from(signature.odd_y_parity).into());
+        }
+        felts
+    }
+
+    fn invoke_transaction(
+        sender: Address,
+        calldata: Vec<FieldElement>,
+        signature: Vec<FieldElement>,
+    ) -> StarknetTransaction {
+        let StarknetTransaction::Invoke(InvokeTransaction::V1(mut tx)) = starknet_transaction_fixture() else {
+            panic!("Expected invoke v1 transaction");
+        };
+        tx.sender_address = starknet_address(sender);
+        tx.calldata = calldata;
+        tx.signature = signature;
+        StarknetTransaction::Invoke(InvokeTransaction::V1(tx))
+    }
+
+    #[test]
+    fn test_starknet_to_rpc_transaction_recovers_sender() {
+        // Given
+        // Anvil first account
+        let private_key = B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let sender = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+
+        for transaction in [
+            Transaction::Legacy(TxLegacy { chain_id: Some(1_802_203_764), gas_limit: 21_000, ..Default::default() }),
+            Transaction::Eip1559(TxEip1559 { chain_id: 1_802_203_764, gas_limit: 21_000, ..Default::default() }),
+        ] {
+            let signature = sign_message(private_key, transaction.signature_hash()).unwrap();
+            let starknet_transaction =
+                invoke_transaction(sender, kakarot_calldata(&transaction), kakarot_signature(&signature, &transaction));
+
+            // When
+            let rpc_transaction = starknet_to_rpc_transaction(&starknet_transaction, true).unwrap();
+
+            // Then
+            assert_eq!(rpc_transaction.from, sender);
+            let rpc_signature = rpc_transaction.signature.unwrap();
+            assert_eq!(rpc_signature.r, signature.r);
+            assert_eq!(rpc_signature.s, signature.s);
+            // Only the typed transactions carry the parity of the signature as yParity
+            let expected_y_parity = transaction.is_eip1559().then_some(reth_rpc_types::Parity(signature.odd_y_parity));
+            assert_eq!(rpc_signature.y_parity, expected_y_parity);
+        }
+    }
+
+    #[test]
+    fn test_starknet_to_rpc_transaction_destination() {
+        // Given
+        let private_key = B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let sender = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let to = Address::from_str("0x0000000000000000000000000000000000000002").unwrap();
+
+        for (kind, expected_to) in [(TxKind::Call(to), Some(to)), (TxKind::Create, None)] {
+            let transaction = Transaction::Eip1559(TxEip1559 {
+                chain_id: 1_802_203_764,
+                gas_limit: 100_000,
+                to: kind,
+                input: Bytes::from_str("0x6080").unwrap(),
+                ..Default::default()
+            });
+            let signature = sign_message(private_key, transaction.signature_hash()).unwrap();
+            let starknet_transaction =
+                invoke_transaction(sender, kakarot_calldata(&transaction), kakarot_signature(&signature, &transaction));
+
+            // When
+            let rpc_transaction = starknet_to_rpc_transaction(&starknet_transaction, true).unwrap();
+
+            // Then
+            // A contract creation has no destination, from which its receipt is marked as a deployment
+            assert_eq!(rpc_transaction.to, expected_to);
+        }
+    }
+
+    #[test]
+    fn test_starknet_to_rpc_transaction_v3_keeps_signed_fee_fields() {
+        // Given
+        let private_key = B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let sender = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1_802_203_764,
+            gas_limit: 21_000,
+            max_fee_per_gas: 7,
+            max_priority_fee_per_gas: 2,
+            ..Default::default()
+        });
+        let signature = sign_message(private_key, transaction.signature_hash()).unwrap();
+        let StarknetTransaction::Invoke(InvokeTransaction::V1(tx)) =
+            invoke_transaction(sender, kakarot_calldata(&transaction), kakarot_signature(&signature, &transaction))
+        else {
+            panic!("Expected invoke v1 transaction");
+        };
+        let starknet_transaction = StarknetTransaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+            transaction_hash: tx.transaction_hash,
+            sender_address: tx.sender_address,
+            calldata: tx.calldata,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            resource_bounds: ResourceBoundsMapping {
+                l1_gas: ResourceBounds { max_amount: 1_000_000, max_price_per_unit: 100 },
+                l2_gas: ResourceBounds { max_amount: 0, max_price_per_unit: 0 },
+            },
+            tip: 5,
+            paymaster_data: vec![],
+            account_deployment_data: vec![],
+            nonce_data_availability_mode: DataAvailabilityMode::L1,
+            fee_data_availability_mode: DataAvailabilityMode::L1,
+        }));
+
+        // When
+        let rpc_transaction = starknet_to_rpc_transaction(&starknet_transaction, true).unwrap();
+
+        // Then
+        // The fee fields are covered by the EVM signature, the resource bounds only price the Starknet execution
+        assert_eq!(rpc_transaction.from, sender);
+        assert_eq!(rpc_transaction.gas, 21_000);
+        assert_eq!(rpc_transaction.max_fee_per_gas, Some(7));
+        assert_eq!(rpc_transaction.max_priority_fee_per_gas, Some(2));
+    }
+
+    #[test]
+    fn test_starknet_to_rpc_transaction_signer_mismatch() {
+        // Given
+        let private_key = B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let sender = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let transaction =
+            Transaction::Eip1559(TxEip1559 { chain_id: 1_802_203_764, gas_limit: 21_000, ..Default::default() });
+        let mut signature = sign_message(private_key, transaction.signature_hash()).unwrap();
+        // Tamper with the signature, which makes it recover to another address
+        signature.odd_y_parity = !signature.odd_y_parity;
+        let starknet_transaction =
+            invoke_transaction(sender, kakarot_calldata(&transaction), kakarot_signature(&signature, &transaction));
+
+        // When
+        let strict = starknet_to_rpc_transaction(&starknet_transaction, true);
+        let lenient = starknet_to_rpc_transaction(&starknet_transaction, false);
+
+        // Then
+        assert!(matches!(strict, Err(EthApiError::Signature(SignatureError::SignerMismatch))));
+        assert_ne!(lenient.unwrap().from, sender);
+    }
+
+    #[test]
+    fn test_signature_from_felts_chain_id_overflow() {
+        // Given
+        let transaction = Transaction::Legacy(TxLegacy { chain_id: Some(u64::MAX), ..Default::default() });
+        let signature = vec![FieldElement::ONE, FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO, 37u8.into()];
+
+        // When
+        let signature = signature_from_felts(&signature, &transaction);
+
+        // Then
+        assert!(matches!(signature, Err(EthApiError::Signature(SignatureError::InvalidParity))));
+    }
+
+    #[test]
+    fn test_signature_from_felts_invalid_parity() {
+        // Given
+        let transaction = Transaction::Eip1559(TxEip1559::default());
+        let signature =
+            vec![FieldElement::ONE, FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO, FieldElement::TWO];
+
+        // When
+        let signature = signature_from_felts(&signature, &transaction);
+
+        // Then
+        assert!(matches!(signature, Err(EthApiError::Signature(SignatureError::InvalidParity))));
+    }
+
+    #[test]
+    fn test_signature_from_felts_truncated() {
+        // Given
+        let transaction = Transaction::Eip1559(TxEip1559::default());
+        let signature =
+            [FieldElement::ONE, FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO, FieldElement::ONE];
+
+        for len in 0..signature.len() {
+            // When
+            let truncated = signature_from_felts(&signature[..len], &transaction);
+
+            // Then
+            assert!(matches!(truncated, Err(EthApiError::Signature(SignatureError::MissingSignature))));
+        }
+    }
+
+    #[test]
+    fn test_filter_starknet_into_eth_txs_skips_declare() {
+        // Given
+        let private_key = B256::from_str("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let sender = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        let transaction =
+            Transaction::Eip1559(TxEip1559 { chain_id: 1_802_203_764, gas_limit: 21_000, ..Default::default() });
+        let signature = sign_message(private_key, transaction.signature_hash()).unwrap();
+        let invoke =
+            invoke_transaction(sender, kakarot_calldata(&transaction), kakarot_signature(&signature, &transaction));
+        let declare: StarknetTransaction = serde_json::from_value(json!({
+            "type": "DECLARE",
+            "version": "0x1",
+            "transaction_hash": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "class_hash": "0x2",
+            "sender_address": "0x3"
+        }))
+        .unwrap();
+
+        // When
+        let transactions = filter_starknet_into_eth_txs(&[declare, invoke], fixture_kakarot_address(), true, false);
+
+        // Then
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].from, sender);
+    }
+
+    fn l1_handler_deposit() -> StarknetTransaction {
+        serde_json::from_value(json!({
+            "type": "L1_HANDLER",
+            "version": "0x0",
+            "transaction_hash": "0x1234",
+            "nonce": "0x7",
+            "contract_address": "0x2fcc35a38e2b535ccecf635e67fdb558cb24924e011a5b6851ec7e3f03f8473",
+            "entry_point_selector": "0x1",
+            "calldata": [
+                "0x8453fc6cd1bcfe8d4dfc069c400b433054d47bdc",
+                "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "0x70997970c51812dc3a010c7d01b50e0d17dc79c8",
+                "0xde0b6b3a7640000",
+                "0x2",
+                "0xab",
+                "0xcd"
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_l1_handler_to_rpc_transaction_deposit() {
+        // Given
+        let StarknetTransaction::L1Handler(deposit) = l1_handler_deposit() else {
+            panic!("Expected L1 handler transaction");
+        };
+
+        // When
+        let transaction = l1_handler_to_rpc_transaction(&deposit).unwrap();
+
+        // Then
+        assert_eq!(transaction.hash, B256::from(U256::from(0x1234)));
+        assert_eq!(transaction.nonce, 7);
+        assert_eq!(transaction.from, Address::from_str("0x8453FC6Cd1bCfE8D4dFC069C400B433054d47bDc").unwrap());
+        assert_eq!(transaction.to, Some(Address::from_str("0x70997970C51812dc3A010C7d01b50e0d17dc79C8").unwrap()));
+        assert_eq!(transaction.value, U256::from(1_000_000_000_000_000_000_u64));
+        assert_eq!(transaction.input, Bytes::from_str("0xabcd").unwrap());
+        assert!(transaction.signature.is_none());
+    }
+
+    #[test]
+    fn test_l1_handler_to_rpc_transaction_truncated() {
+        // Given
+        let StarknetTransaction::L1Handler(deposit) = l1_handler_deposit() else {
+            panic!("Expected L1 handler transaction");
+        };
+        let short_prefix = L1HandlerTransaction { calldata: deposit.calldata[..4].to_vec(), ..deposit.clone() };
+        let short_data = L1HandlerTransaction { calldata: deposit.calldata[..6].to_vec(), ..deposit };
+
+        // When
+        let short_prefix = l1_handler_to_rpc_transaction(&short_prefix);
+        let short_data = l1_handler_to_rpc_transaction(&short_data);
+
+        // Then
+        assert!(matches!(short_prefix, Err(EthereumDataFormatError::TransactionConversionError)));
+        assert!(matches!(short_data, Err(EthereumDataFormatError::TransactionConversionError)));
+    }
+
+    #[test]
+    fn test_filter_starknet_into_eth_txs_l1_handler() {
+        // Given
+        let transactions = [l1_handler_deposit()];
+
+        // When
+        let without_l1_handlers = filter_starknet_into_eth_txs(&transactions, fixture_kakarot_address(), true, false);
+        let with_l1_handlers = filter_starknet_into_eth_txs(&transactions, fixture_kakarot_address(), true, true);
+
+        // Then
+        assert!(without_l1_handlers.is_empty());
+        assert_eq!(with_l1_handlers.len(), 1);
+    }
+
+    #[test]
+    fn test_is_kakarot_transaction_declare_and_deploy() {
+        // Given
+        let declare: StarknetTransaction = serde_json::from_value(json!({
+            "type": "DECLARE",
+            "version": "0x1",
+            "transaction_hash": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "class_hash": "0x2",
+            "sender_address": "0x3"
+        }))
+        .unwrap();
+        let deploy_account: StarknetTransaction = serde_json::from_value(json!({
+            "type": "DEPLOY_ACCOUNT",
+            "version": "0x1",
+            "transaction_hash": "0x1",
+            "max_fee": "0x0",
+            "signature": [],
+            "nonce": "0x0",
+            "contract_address_salt": "0x2",
+            "constructor_calldata": ["0x2fcc35a38e2b535ccecf635e67fdb558cb24924e011a5b6851ec7e3f03f8473"],
+            "class_hash": "0x3"
+        }))
+        .unwrap();
+
+        // When
+        let is_kakarot_declare = is_kakarot_transaction(&declare, fixture_kakarot_address());
+        let is_kakarot_deploy_account = is_kakarot_transaction(&deploy_account, fixture_kakarot_address());
+
+        // Then
+        assert!(!is_kakarot_declare);
+        assert!(!is_kakarot_deploy_account);
+    }
+
     struct RpcTxBuilder {
         tx: reth_rpc_types::Transaction,
     }
@@ -194,4 +990,28 @@ mod tests {
         max_fee_per_gas,
         true
     );
+
+    // Macro to create the tests for rpc transaction to raw bytes encoding
+    macro_rules! test_rpc_transaction_to_raw {
+        ($test_name: ident, $tx_initializer: ident, $gas_price_field: ident, $has_access_list: expr) => {
+            #[test]
+            fn $test_name() {
+                // Given
+                let rpc_tx = $tx_initializer();
+
+                // When
+                let bytes = rpc_transaction_to_raw(rpc_tx.clone()).expect("Failed to encode RPC transaction");
+
+                // Then
+                let tx = TransactionSigned::decode_enveloped(&mut bytes.as_ref()).expect("Failed to decode bytes");
+                assert_common_fields!(tx, rpc_tx, $gas_price_field, $has_access_list);
+            }
+        };
+    }
+
+    test_rpc_transaction_to_raw!(test_legacy_transaction_to_raw, legacy_rpc_transaction, gas_price, false);
+
+    test_rpc_transaction_to_raw!(test_eip2930_transaction_to_raw, eip2930_rpc_transaction, gas_price, true);
+
+    test_rpc_transaction_to_raw!(test_eip1559_transaction_to_raw, eip1559_rpc_transaction, max_fee_per_gas, true);
 }
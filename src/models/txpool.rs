@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use reth_primitives::Address;
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// A txpool response whose buckets are serialized in increasing nonce order. The buckets
+/// are keyed by decimal nonces, which would otherwise be ordered as strings, "10" before "9".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonceOrdered<T>(pub T);
+
+/// Serializes the transactions of a bucket ordered by nonce.
+struct Bucket<'a, T>(&'a BTreeMap<String, T>);
+
+impl<T: Serialize> Serialize for Bucket<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut transactions: Vec<_> = self.0.iter().collect();
+        // Nonces have no leading zeros, so a shorter nonce is a smaller one
+        transactions.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        serializer.collect_map(transactions)
+    }
+}
+
+/// Serializes the buckets of each sender ordered by nonce.
+struct Buckets<'a, T>(&'a BTreeMap<Address, BTreeMap<String, T>>);
+
+impl<T: Serialize> Serialize for Buckets<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(sender, bucket)| (sender, Bucket(bucket))))
+    }
+}
+
+impl Serialize for NonceOrdered<TxpoolContent> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut content = serializer.serialize_struct("TxpoolContent", 2)?;
+        content.serialize_field("pending", &Buckets(&self.0.pending))?;
+        content.serialize_field("queued", &Buckets(&self.0.queued))?;
+        content.end()
+    }
+}
+
+impl Serialize for NonceOrdered<TxpoolContentFrom> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut content = serializer.serialize_struct("TxpoolContentFrom", 2)?;
+        content.serialize_field("pending", &Bucket(&self.0.pending))?;
+        content.serialize_field("queued", &Bucket(&self.0.queued))?;
+        content.end()
+    }
+}
+
+impl Serialize for NonceOrdered<TxpoolInspect> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut inspect = serializer.serialize_struct("TxpoolInspect", 2)?;
+        inspect.serialize_field("pending", &Buckets(&self.0.pending))?;
+        inspect.serialize_field("queued", &Buckets(&self.0.queued))?;
+        inspect.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::Transaction;
+
+    #[test]
+    fn test_nonce_ordered_content() {
+        // Given
+        let sender = Address::repeat_byte(1);
+        let mut content = TxpoolContent::default();
+        for nonce in [10, 9, 100, 0] {
+            let transaction = Transaction { nonce, from: sender, ..Default::default() };
+            content.pending.entry(sender).or_default().insert(nonce.to_string(), transaction);
+        }
+
+        // When
+        let serialized = serde_json::to_string(&NonceOrdered(content)).unwrap();
+
+        // Then
+        let positions: Vec<_> =
+            ["\"0\":", "\"9\":", "\"10\":", "\"100\":"].iter().map(|key| serialized.find(key).unwrap()).collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(serialized.contains("\"queued\":{}"));
+    }
+}
@@ -138,7 +138,7 @@ mod tests {
     use lazy_static::lazy_static;
 
     use crate::{
-        eth_provider::utils::split_u256,
+        eth_provider::utils::{join_u256, split_u256},
         test_utils::{constants::ACCOUNT_STORAGE, katana::genesis::Initialized},
     };
 
@@ -186,7 +186,7 @@ mod tests {
                 let high = U256::from_be_slice(
                     contract.storage.as_ref().unwrap().get(&(key + 1u8.into())).unwrap().to_bytes_be().as_slice(),
                 );
-                let actual_value = low + (high << 128);
+                let actual_value = join_u256(low, high);
                 assert_eq!(actual_value, value);
             }
         }
@@ -20,6 +20,7 @@ use crate::eth_provider::database::types::{
 };
 use crate::eth_provider::utils::{format_hex, into_filter};
 use crate::eth_provider::{
+    config::KakarotConfig,
     constant::{HASH_HEX_STRING_LEN, U64_HEX_STRING_LEN},
     provider::EthDataProvider,
 };
@@ -136,8 +137,9 @@ impl<'a> Katana {
         let mock_data = (*mongo_fuzzer.documents()).clone();
 
         // Create a new EthDataProvider instance with the initialized database and Starknet provider.
+        let config = KakarotConfig::from_env().expect("Failed to read KakarotConfig");
         let eth_provider = Arc::new(
-            EthDataProvider::new(database, starknet_provider).await.expect("Failed to create EthDataProvider"),
+            EthDataProvider::new(database, starknet_provider, config).await.expect("Failed to create EthDataProvider"),
         );
 
         // Create a new Kakarot EOA instance with the private key and EthDataProvider instance.
@@ -3,29 +3,9 @@ use crate::eth_rpc::config::RPCConfig;
 use crate::eth_rpc::rpc::KakarotRpcModuleBuilder;
 use crate::eth_rpc::run_server;
 use jsonrpsee::server::ServerHandle;
-use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::net::SocketAddr;
-use tokio::sync::Mutex;
-
-lazy_static! {
-    /// A lazy static mutex for managing the next available port number.
-    ///
-    /// It ensures thread-safe access to the next port number.
-    static ref NEXT_PORT: Mutex<u16> = Mutex::new(3030);
-}
-
-/// Asynchronously gets the next available port number.
-///
-/// This function locks the `NEXT_PORT` mutex to ensure exclusive access
-/// to the next port number and increments it for subsequent calls.
-async fn get_next_port() -> u16 {
-    let mut port = NEXT_PORT.lock().await;
-    let next_port = *port;
-    *port += 1;
-    next_port
-}
 
 /// Sets up the environment for Kakarot RPC integration tests by deploying the Kakarot contracts
 /// and starting the Kakarot RPC server.
@@ -45,7 +25,8 @@ async fn get_next_port() -> u16 {
 /// # Returns
 ///
 /// This function returns a Result containing a tuple with the server's address and a handle to
-/// stop the server upon successful execution.
+/// stop the server upon successful execution. The server is bound to an ephemeral port chosen by
+/// the OS, which is read from the returned address.
 ///
 /// The function may return an Err variant of `eyre::Report` if there are issues with deploying the
 /// Kakarot contracts, creating the clients, or running the RPC server.
@@ -81,9 +62,9 @@ pub async fn start_kakarot_rpc_server(katana: &Katana) -> Result<(SocketAddr, Se
     Ok(run_server(
         KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module()?,
         #[cfg(feature = "testing")]
-        RPCConfig::new_test_config_from_port(get_next_port().await),
+        RPCConfig::new_test_config_from_port(0),
         #[cfg(not(feature = "testing"))]
-        RPCConfig::from_port(get_next_port().await),
+        RPCConfig::from_port(0)?,
     )
     .await?)
 }
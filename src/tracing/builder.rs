@@ -55,11 +55,11 @@ impl<P: EthereumProvider + Send + Sync + Clone> TracerBuilder<P, Floating> {
             self.eth_provider.transaction_by_hash(transaction_hash).await?.ok_or(EthApiError::TransactionNotFound)?;
 
         // we can't trace a pending transaction
-        if transaction.block_number.is_none() {
+        let Some(block_number) = transaction.block_number else {
             return Err(EthApiError::UnknownBlock);
-        }
+        };
 
-        self.with_block_id(BlockId::Number(transaction.block_number.unwrap().into())).await
+        self.with_block_id(BlockId::Number(block_number.into())).await
     }
 
     /// Fetches a block from the Ethereum provider given a block id
@@ -74,8 +74,8 @@ impl<P: EthereumProvider + Send + Sync + Clone> TracerBuilder<P, Floating> {
         }
         .ok_or(EthApiError::UnknownBlock)?;
 
-        // we can't trace a pending block
-        if block.header.hash.unwrap_or_default().is_zero() {
+        // we can't trace a pending block, which has no hash or number
+        if block.header.hash.unwrap_or_default().is_zero() || block.header.number.is_none() {
             return Err(EthApiError::UnknownBlock);
         }
 
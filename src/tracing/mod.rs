@@ -234,6 +234,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::eth_provider::config::KakarotConfig;
     use crate::eth_provider::database::Database;
     use crate::eth_provider::provider::EthDataProvider;
     use builder::TracerBuilder;
@@ -278,7 +279,8 @@ mod tests {
             ),
         );
 
-        let eth_provider = Arc::new(EthDataProvider::new(db, starknet_provider).await.unwrap());
+        let eth_provider =
+            Arc::new(EthDataProvider::new(db, starknet_provider, KakarotConfig::default()).await.unwrap());
         let tracer = TracerBuilder::new(eth_provider)
             .await
             .unwrap()
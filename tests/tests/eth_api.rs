@@ -0,0 +1,589 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+use http::HeaderValue;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use kakarot_rpc::eth_provider::constant::HASH_HEX_STRING_LEN;
+use kakarot_rpc::eth_provider::database::types::transaction::StoredStarknetTransactionHash;
+use kakarot_rpc::eth_provider::provider::EthereumProvider;
+use kakarot_rpc::eth_provider::utils::into_filter;
+use kakarot_rpc::eth_rpc::api::eth_api::EthApiServer;
+use kakarot_rpc::eth_rpc::config::{CorsConfig, RPCConfig};
+use kakarot_rpc::eth_rpc::rpc::KakarotRpcModuleBuilder;
+use kakarot_rpc::eth_rpc::servers::eth_rpc::KakarotEthRpc;
+use kakarot_rpc::eth_rpc::{run_server, shutdown_server};
+use kakarot_rpc::models::felt::Felt252Wrapper;
+use kakarot_rpc::test_utils::evm_contract::KakarotEvmContract;
+use kakarot_rpc::test_utils::fixtures::{counter, katana, setup};
+use kakarot_rpc::test_utils::katana::Katana;
+use kakarot_rpc::test_utils::mongo::{BLOCK_HASH, BLOCK_NUMBER, EIP1599_TX_HASH};
+use kakarot_rpc::test_utils::rpc::start_kakarot_rpc_server;
+use kakarot_rpc::test_utils::rpc::RawRpcParamsBuilder;
+use reth_primitives::{Address, Bytes, TransactionSigned, B256, U256};
+use rstest::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+async fn request<S: Serialize>(method: &str, port: u16, params: Vec<S>) -> Value {
+    let mut body_builder = RawRpcParamsBuilder::new(method);
+    for p in params {
+        body_builder = body_builder.add_param(p);
+    }
+
+    let res = reqwest::Client::new()
+        .post(format!("http://localhost:{port}"))
+        .header("Content-Type", "application/json")
+        .body(body_builder.build())
+        .send()
+        .await
+        .expect("Failed to call Eth RPC");
+    let response = res.text().await.expect("Failed to get response body");
+    let raw: Value = serde_json::from_str(&response).expect("Failed to deserialize response body");
+    raw["result"].clone()
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_raw_transaction_by_block_and_index(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+    let expected = katana
+        .eth_provider()
+        .transaction_by_block_hash_and_index(*BLOCK_HASH, 0usize.into())
+        .await
+        .expect("Failed to get transaction")
+        .expect("Missing transaction");
+
+    // When
+    let by_hash = request(
+        "eth_getRawTransactionByBlockHashAndIndex",
+        port,
+        vec![Value::from(format!("0x{:064x}", *BLOCK_HASH)), Value::from("0x0")],
+    )
+    .await;
+    let by_number = request(
+        "eth_getRawTransactionByBlockNumberAndIndex",
+        port,
+        vec![Value::from(format!("0x{BLOCK_NUMBER:x}")), Value::from("0x0")],
+    )
+    .await;
+
+    // Then
+    for result in [by_hash, by_number] {
+        let raw: Bytes = serde_json::from_value(result).expect("Failed to deserialize result");
+        let transaction = TransactionSigned::decode_enveloped(&mut raw.as_ref()).expect("Failed to decode transaction");
+        assert_eq!(transaction.hash, expected.hash);
+    }
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_raw_transaction_by_block_and_index_out_of_range(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+
+    // When
+    let by_hash = request(
+        "eth_getRawTransactionByBlockHashAndIndex",
+        port,
+        vec![Value::from(format!("0x{:064x}", *BLOCK_HASH)), Value::from("0xffff")],
+    )
+    .await;
+    let by_number = request(
+        "eth_getRawTransactionByBlockNumberAndIndex",
+        port,
+        vec![Value::from(format!("0x{BLOCK_NUMBER:x}")), Value::from("0xffff")],
+    )
+    .await;
+
+    // Then
+    assert!(by_hash.is_null());
+    assert!(by_number.is_null());
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_graceful_shutdown_drains_in_flight_requests(_setup: ()) {
+    // Given
+    // A method which is slow on purpose, notifying when it started handling the call
+    let started = Arc::new(Notify::new());
+    let mut module = RpcModule::new(started.clone());
+    module
+        .register_async_method("test_slow", |_, started| async move {
+            started.notify_one();
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok::<_, ErrorObjectOwned>("done".to_string())
+        })
+        .expect("Failed to register the slow method");
+    let (server_addr, server_handle) =
+        run_server(module, RPCConfig::new_test_config_from_port(0)).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+    let in_flight = tokio::spawn(async move { request::<Value>("test_slow", port, vec![]).await });
+    started.notified().await;
+
+    // When
+    let still_running = !in_flight.is_finished();
+    let drained = shutdown_server(server_handle, Duration::from_secs(10)).await;
+
+    // Then
+    assert!(still_running);
+    assert!(drained);
+    let result = in_flight.await.expect("In-flight request failed");
+    assert_eq!(result, json!("done"));
+}
+
+async fn batch_request(port: u16, methods: &[&str]) -> Value {
+    let body = format!(
+        "[{}]",
+        methods
+            .iter()
+            .enumerate()
+            .map(|(id, method)| RawRpcParamsBuilder::new(*method).set_id(i32::try_from(id).unwrap()).build())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+
+    let res = reqwest::Client::new()
+        .post(format!("http://localhost:{port}"))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to call Eth RPC");
+    let response = res.text().await.expect("Failed to get response body");
+    serde_json::from_str(&response).expect("Failed to deserialize response body")
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_request(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let (server_addr, server_handle) =
+        run_server(module, RPCConfig::new_test_config_from_port(0).with_max_batch_size(3))
+            .await
+            .expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+
+    // When
+    let valid = batch_request(port, &["eth_chainId", "eth_blockNumber", "net_version"]).await;
+    let oversized = batch_request(port, &["eth_chainId", "eth_blockNumber", "net_version", "eth_gasPrice"]).await;
+
+    // Then
+    let results = valid.as_array().expect("Expected a batch response");
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|result| result.get("result").is_some()));
+    assert!(oversized.get("error").is_some());
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_response_compression(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let (server_addr, server_handle) =
+        run_server(module, RPCConfig::new_test_config_from_port(0).with_compression(512))
+            .await
+            .expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+    let content_encoding = |body: String| async move {
+        let res = reqwest::Client::new()
+            .post(format!("http://localhost:{port}"))
+            .header("Content-Type", "application/json")
+            .header("Accept-Encoding", "gzip")
+            .body(body)
+            .send()
+            .await
+            .expect("Failed to call Eth RPC");
+        res.headers().get("content-encoding").map(|encoding| encoding.to_str().unwrap().to_string())
+    };
+
+    // When
+    let large = content_encoding(
+        RawRpcParamsBuilder::new("eth_getBlockByNumber")
+            .add_param(format!("0x{BLOCK_NUMBER:x}"))
+            .add_param(true)
+            .build(),
+    )
+    .await;
+    let small = content_encoding(RawRpcParamsBuilder::new("eth_chainId").build()).await;
+
+    // Then
+    assert_eq!(large.as_deref(), Some("gzip"));
+    assert!(small.is_none());
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cors_allowed_origins(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let cors = CorsConfig {
+        allowed_origins: Some(vec![HeaderValue::from_static("https://allowed.example")]),
+        ..Default::default()
+    };
+    let (server_addr, server_handle) = run_server(module, RPCConfig::new_test_config_from_port(0).with_cors(cors))
+        .await
+        .expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+    let allow_origin = |origin: &'static str| async move {
+        let res = reqwest::Client::new()
+            .post(format!("http://localhost:{port}"))
+            .header("Content-Type", "application/json")
+            .header("Origin", origin)
+            .body(RawRpcParamsBuilder::new("eth_chainId").build())
+            .send()
+            .await
+            .expect("Failed to call Eth RPC");
+        res.headers().get("access-control-allow-origin").map(|origin| origin.to_str().unwrap().to_string())
+    };
+
+    // When
+    let allowed = allow_origin("https://allowed.example").await;
+    let disallowed = allow_origin("https://disallowed.example").await;
+
+    // Then
+    assert_eq!(allowed.as_deref(), Some("https://allowed.example"));
+    assert!(disallowed.is_none());
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unknown_method(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    assert!(!module.method_names().any(|name| name == "eth_bogusMethod"));
+
+    for method in ["eth_bogusMethod", "eth_coinbase"] {
+        // When
+        let (response, _) = module
+            .raw_json_request(&RawRpcParamsBuilder::new(method).build(), 1)
+            .await
+            .expect("Failed to call RPC module");
+
+        // Then
+        let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+        assert_eq!(response["error"]["code"], -32601, "method {method}");
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_state_overrides(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let module =
+        KakarotRpcModuleBuilder::new(counter.0.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let counter_address: Felt252Wrapper = counter.1.evm_address.into();
+    let counter_address: Address = counter_address.try_into().unwrap();
+    // selector of "function count()"
+    let request = json!({"to": counter_address, "input": "0x06661abd"});
+    let call = |overrides: Value| {
+        RawRpcParamsBuilder::new("eth_call").add_param(&request).add_param("latest").add_param(overrides).build()
+    };
+
+    // When
+    let (empty, _) = module.raw_json_request(&call(json!({})), 1).await.expect("Failed to call RPC module");
+    let (code, _) = module
+        .raw_json_request(&call(json!({ counter_address.to_string(): {"code": "0x00"} })), 1)
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let empty: Value = serde_json::from_str(&empty).expect("Failed to deserialize response");
+    assert!(empty["result"].is_string());
+    let code: Value = serde_json::from_str(&code).expect("Failed to deserialize response");
+    assert_eq!(code["error"]["code"], -32603);
+    assert!(code["error"]["message"].as_str().unwrap().contains("state overrides"));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_base_fee_override(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let eth_provider = counter.0.eth_provider();
+    let base_fee = eth_provider.gas_price().await.expect("Failed to get gas price").max(U256::from(1));
+    let module = KakarotRpcModuleBuilder::new(eth_provider).rpc_module().expect("Failed to build RPC module");
+    let counter_address: Felt252Wrapper = counter.1.evm_address.into();
+    let counter_address: Address = counter_address.try_into().unwrap();
+    // selector of "function count()", with a 1559 fee cap of twice the current base fee
+    let request = json!({"to": counter_address, "input": "0x06661abd", "maxFeePerGas": base_fee * U256::from(2)});
+    let call = |overrides: Value| {
+        RawRpcParamsBuilder::new("eth_call")
+            .add_param(&request)
+            .add_param("latest")
+            .add_param(json!({}))
+            .add_param(overrides)
+            .build()
+    };
+
+    // When
+    let (below_cap, _) =
+        module.raw_json_request(&call(json!({"baseFee": base_fee})), 1).await.expect("Failed to call RPC module");
+    let (above_cap, _) = module
+        .raw_json_request(&call(json!({"baseFee": base_fee * U256::from(3)})), 1)
+        .await
+        .expect("Failed to call RPC module");
+    let (other_override, _) =
+        module.raw_json_request(&call(json!({"coinbase": Address::ZERO})), 1).await.expect("Failed to call RPC module");
+
+    // Then
+    let below_cap: Value = serde_json::from_str(&below_cap).expect("Failed to deserialize response");
+    assert!(below_cap["result"].is_string());
+    let above_cap: Value = serde_json::from_str(&above_cap).expect("Failed to deserialize response");
+    assert_eq!(above_cap["error"]["code"], -32000);
+    assert!(above_cap["error"]["message"].as_str().unwrap().contains("base fee"));
+    let other_override: Value = serde_json::from_str(&other_override).expect("Failed to deserialize response");
+    assert!(other_override["error"]["message"].as_str().unwrap().contains("block overrides"));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_quantity_serialization(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let module = KakarotRpcModuleBuilder::new(eth_provider.clone()).rpc_module().expect("Failed to build RPC module");
+    let chain_id = eth_provider.chain_id().await.unwrap().unwrap().to::<u64>();
+    let block_number = eth_provider.block_number().await.unwrap().to::<u64>();
+
+    for (method, expected) in [("eth_chainId", chain_id), ("eth_blockNumber", block_number)] {
+        // When
+        let (response, _) = module
+            .raw_json_request(&RawRpcParamsBuilder::new(method).build(), 1)
+            .await
+            .expect("Failed to call RPC module");
+
+        // Then
+        // Quantities are serialized as compact hex strings, without leading zeroes
+        let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+        assert_eq!(response["result"], format!("{expected:#x}"), "method {method}");
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transaction_by_block_and_index_bounds(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let block_hash = format!("0x{:064x}", *BLOCK_HASH);
+    let block_number = format!("0x{BLOCK_NUMBER:x}");
+    let module = &module;
+    let call = |method: &str, block: &str, index: &str| {
+        let request = RawRpcParamsBuilder::new(method).add_param(block).add_param(index).build();
+        async move {
+            let (response, _) = module.raw_json_request(&request, 1).await.expect("Failed to call RPC module");
+            serde_json::from_str::<Value>(&response).expect("Failed to deserialize response")
+        }
+    };
+
+    for (method, block) in [
+        ("eth_getTransactionByBlockHashAndIndex", &block_hash),
+        ("eth_getTransactionByBlockNumberAndIndex", &block_number),
+    ] {
+        // When
+        let first = call(method, block, "0x0").await;
+        let huge = call(method, block, "0xffffffffffffffff").await;
+        let malformed = call(method, block, "0xzz").await;
+        let negative = call(method, block, "-0x1").await;
+
+        // Then
+        assert!(first["result"]["hash"].is_string(), "method {method}");
+        assert!(huge["result"].is_null(), "method {method}");
+        assert_eq!(malformed["error"]["code"], -32602, "method {method}");
+        assert_eq!(negative["error"]["code"], -32602, "method {method}");
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_block_hash_exclusive(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let block_hash = format!("0x{:064x}", *BLOCK_HASH);
+    let get_logs = |filter: Value| RawRpcParamsBuilder::new("eth_getLogs").add_param(filter).build();
+
+    // When
+    let (by_hash, _) = module
+        .raw_json_request(&get_logs(json!({"blockHash": block_hash})), 1)
+        .await
+        .expect("Failed to call RPC module");
+    let (conflict, _) = module
+        .raw_json_request(&get_logs(json!({"blockHash": block_hash, "fromBlock": "0x0"})), 1)
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let by_hash: Value = serde_json::from_str(&by_hash).expect("Failed to deserialize response");
+    assert!(by_hash["result"].is_array());
+    // `blockHash` cannot be combined with a block range
+    let conflict: Value = serde_json::from_str(&conflict).expect("Failed to deserialize response");
+    assert_eq!(conflict["error"]["code"], -32602);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_address(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let methods = [
+        ("eth_getBalance", vec![json!("0x1234"), json!("latest")]),
+        ("eth_getCode", vec![json!("0x1234"), json!("latest")]),
+        ("eth_getStorageAt", vec![json!("0xzz"), json!("0x0"), json!("latest")]),
+        ("eth_getTransactionCount", vec![json!("not an address"), json!("latest")]),
+    ];
+
+    for (method, params) in methods {
+        // When
+        let mut builder = RawRpcParamsBuilder::new(method);
+        for param in params {
+            builder = builder.add_param(param);
+        }
+        let (response, _) = module.raw_json_request(&builder.build(), 1).await.expect("Failed to call RPC module");
+
+        // Then
+        // Malformed addresses are rejected when the parameters are parsed
+        let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+        assert_eq!(response["error"]["code"], -32602, "method {method}");
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receipt_starknet_transaction_hash(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let starknet_hash = B256::repeat_byte(0x12);
+    let hashes = StoredStarknetTransactionHash { eth_hash: *EIP1599_TX_HASH, starknet_hash };
+    eth_provider
+        .database()
+        .update_one(hashes, into_filter("ethHash", &*EIP1599_TX_HASH, HASH_HEX_STRING_LEN), true)
+        .await
+        .expect("Failed to store the Starknet hash");
+    let request = RawRpcParamsBuilder::new("eth_getTransactionReceipt").add_param(*EIP1599_TX_HASH).build();
+
+    for debug_fields in [false, true] {
+        let module = KakarotEthRpc::new(eth_provider.clone()).with_debug_fields(debug_fields).into_rpc();
+
+        // When
+        let (response, _) = module.raw_json_request(&request, 1).await.expect("Failed to call RPC module");
+
+        // Then
+        let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+        assert_eq!(response["result"]["transactionHash"], json!(*EIP1599_TX_HASH));
+        if debug_fields {
+            assert_eq!(response["result"]["starknetTransactionHash"], json!(starknet_hash));
+        } else {
+            assert!(response["result"].get("starknetTransactionHash").is_none());
+        }
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unsupported_extension_method_not_found(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let body = RawRpcParamsBuilder::new("eth_resolveName").add_param("vitalik.eth").build();
+
+    // When
+    let response = tokio::time::timeout(
+        Duration::from_secs(5),
+        reqwest::Client::new()
+            .post(format!("http://localhost:{}", server_addr.port()))
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send(),
+    )
+    .await
+    .expect("Unsupported method should not hang")
+    .expect("Failed to call Eth RPC");
+
+    // Then
+    let response = response.text().await.expect("Failed to get response body");
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response body");
+    assert_eq!(response["error"]["code"], -32601);
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_server_end_to_end(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    assert_ne!(server_addr.port(), 0);
+    let client = reqwest::Client::new();
+    let call = |body: String| {
+        let request = client
+            .post(format!("http://localhost:{}", server_addr.port()))
+            .header("Content-Type", "application/json")
+            .body(body);
+        async move {
+            let response = request.send().await.expect("Failed to call Eth RPC");
+            let response = response.text().await.expect("Failed to get response body");
+            serde_json::from_str::<Value>(&response).expect("Failed to deserialize response body")
+        }
+    };
+
+    // When
+    let chain_id = call(RawRpcParamsBuilder::new("eth_chainId").set_id(1).build()).await;
+    let block_number = call(RawRpcParamsBuilder::new("eth_blockNumber").set_id(2).build()).await;
+    let block = call(
+        RawRpcParamsBuilder::new("eth_getBlockByNumber")
+            .add_param(format!("0x{BLOCK_NUMBER:x}"))
+            .add_param(false)
+            .set_id(3)
+            .build(),
+    )
+    .await;
+
+    // Then
+    for (response, id) in [(&chain_id, 1), (&block_number, 2), (&block, 3)] {
+        assert_eq!(response["jsonrpc"], "2.0");
+        assert_eq!(response["id"], id);
+        assert!(response.get("error").is_none(), "unexpected error: {response}");
+    }
+    let chain_id = chain_id["result"].as_str().expect("Chain id should be a quantity");
+    assert!(chain_id.starts_with("0x"));
+    assert!(u64::from_str_radix(&chain_id[2..], 16).is_ok());
+    let block_number = block_number["result"].as_str().expect("Block number should be a quantity");
+    assert!(u64::from_str_radix(block_number.trim_start_matches("0x"), 16).is_ok());
+    assert_eq!(block["result"]["number"], format!("0x{BLOCK_NUMBER:x}"));
+    assert_eq!(block["result"]["hash"], format!("0x{:064x}", *BLOCK_HASH));
+    assert!(block["result"]["transactions"].is_array());
+
+    drop(server_handle);
+}
@@ -1,28 +1,46 @@
 #![allow(clippy::used_underscore_binding)]
 #![cfg(feature = "testing")]
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use kakarot_rpc::eth_provider::constant::{HASH_HEX_STRING_LEN, STARKNET_MODULUS, TRANSACTION_MAX_RETRIES};
-use kakarot_rpc::eth_provider::database::types::transaction::{StoredPendingTransaction, StoredTransaction};
+use kakarot_rpc::config::KakarotRpcConfig;
+use kakarot_rpc::eth_provider::config::KakarotConfig;
+use kakarot_rpc::eth_provider::constant::{
+    BLOCK_NUMBER_HEX_STRING_LEN, HASH_HEX_STRING_LEN, KAKAROT_SYSTEM_EVENTS, STARKNET_MODULUS, TRANSACTION_MAX_RETRIES,
+};
+use kakarot_rpc::eth_provider::database::types::header::StoredHeader;
+use kakarot_rpc::eth_provider::database::types::log::StoredLog;
+use kakarot_rpc::eth_provider::database::types::receipt::StoredTransactionReceipt;
+use kakarot_rpc::eth_provider::database::types::transaction::{
+    StoredPendingTransaction, StoredStarknetTransactionHash, StoredTransaction,
+};
+use kakarot_rpc::eth_provider::error::{EthApiError, EvmError, KakarotError, TransactionError};
 use kakarot_rpc::eth_provider::provider::EthereumProvider;
-use kakarot_rpc::eth_provider::utils::into_filter;
+use kakarot_rpc::eth_provider::utils::{into_filter, keccak::event_topic};
+use kakarot_rpc::models::block::PendingBlockHash;
 use kakarot_rpc::models::felt::Felt252Wrapper;
 use kakarot_rpc::test_utils::eoa::Eoa;
 use kakarot_rpc::test_utils::evm_contract::{EvmContract, TransactionInfo, TxCommonInfo, TxLegacyInfo};
 use kakarot_rpc::test_utils::fixtures::{contract_empty, counter, katana, setup};
-use kakarot_rpc::test_utils::mongo::{BLOCK_HASH, BLOCK_NUMBER};
+use kakarot_rpc::test_utils::mongo::{BLOCK_HASH, BLOCK_NUMBER, EIP1599_TX_HASH, LEGACY_TX_HASH};
 use kakarot_rpc::test_utils::tx_waiter::watch_tx;
 use kakarot_rpc::test_utils::{evm_contract::KakarotEvmContract, katana::Katana};
+use kakarot_rpc::tracing::builder::TracerBuilder;
 use reth_primitives::transaction::Signature;
 use reth_primitives::{
-    sign_message, Address, BlockNumberOrTag, Bytes, Transaction, TransactionSigned, TxEip1559, TxKind, B256, U256, U64,
+    keccak256, logs_bloom, sign_message, Address, BlockId, BlockNumberOrTag, Bytes, Transaction, TransactionSigned,
+    TxEip1559, TxKind, TxType, B256, B64, KECCAK_EMPTY, U256, U64,
 };
 use reth_rpc_types::request::TransactionInput;
 use reth_rpc_types::serde_helpers::JsonStorageKey;
-use reth_rpc_types::{Filter, FilterBlockOption, FilterChanges, Log, RpcBlockHash, Topic, TransactionRequest};
+use reth_rpc_types::{
+    BlockTransactions, Filter, FilterBlockOption, FilterChanges, Log, RpcBlockHash, Topic, TransactionRequest,
+};
 use rstest::*;
 use starknet::core::types::BlockTag;
+use starknet::core::utils::cairo_short_string_to_felt;
+use starknet::providers::Provider as _;
 use starknet_crypto::FieldElement;
 
 #[rstest]
@@ -57,6 +75,70 @@ async fn test_chain_id(#[future] katana: Katana, _setup: ()) {
     assert_eq!(chain_id, U64::from(0x7465_7374_u64));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_check_kakarot_class_hash(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let class_hash = eth_provider
+        .starknet_provider()
+        .get_class_hash_at(starknet::core::types::BlockId::Tag(BlockTag::Latest), eth_provider.kakarot_address())
+        .await
+        .unwrap();
+
+    // When
+    let matching = eth_provider.check_kakarot_class_hash(class_hash).await;
+    let mismatching = eth_provider.check_kakarot_class_hash(class_hash + FieldElement::ONE).await;
+
+    // Then
+    assert!(matching.is_ok());
+    assert!(mismatching.is_err());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_warm_up(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let starknet_chain_id = eth_provider.starknet_provider().chain_id().await.unwrap();
+
+    // When
+    let warm_up = eth_provider.warm_up().await;
+    // The Starknet provider isn't queried anymore for the chain id
+    let chain_id = eth_provider.chain_id().await.unwrap().unwrap();
+
+    // Then
+    assert!(warm_up.is_ok());
+    let expected: u64 = (FieldElement::from(u32::MAX) & starknet_chain_id).try_into().unwrap();
+    assert_eq!(chain_id, U64::from(expected));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_check_starknet_chain_id(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let starknet_provider = eth_provider.starknet_provider();
+    let config = KakarotRpcConfig::default();
+    let matching_config =
+        KakarotRpcConfig::default().with_expected_starknet_chain_id(cairo_short_string_to_felt("kaka_test").unwrap());
+    let mismatching_config =
+        KakarotRpcConfig::default().with_expected_starknet_chain_id(cairo_short_string_to_felt("SN_MAIN").unwrap());
+
+    // When
+    let unset = config.check_starknet_chain_id(starknet_provider).await;
+    let matching = matching_config.check_starknet_chain_id(starknet_provider).await;
+    let mismatching = mismatching_config.check_starknet_chain_id(starknet_provider).await;
+
+    // Then
+    assert!(unset.is_ok());
+    assert!(matching.is_ok());
+    assert!(mismatching.is_err());
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -72,6 +154,102 @@ async fn test_block_by_hash(#[future] katana: Katana, _setup: ()) {
     assert_eq!(block.inner.header, katana.header_by_hash(block_hash).unwrap());
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_transactions_follow_full_flag(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+
+    for full in [false, true] {
+        // When
+        let by_hash = eth_provider.block_by_hash(*BLOCK_HASH, full).await.unwrap().unwrap();
+        let by_number =
+            eth_provider.block_by_number(BlockNumberOrTag::Number(BLOCK_NUMBER), full).await.unwrap().unwrap();
+
+        // Then
+        for block in [by_hash, by_number] {
+            match block.inner.transactions {
+                BlockTransactions::Hashes(hashes) => {
+                    assert!(!full);
+                    assert!(!hashes.is_empty());
+                }
+                BlockTransactions::Full(transactions) => {
+                    assert!(full);
+                    assert!(!transactions.is_empty());
+                }
+                BlockTransactions::Uncle => panic!("Unexpected uncle block"),
+            }
+        }
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_cache(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let cached_provider = (*eth_provider)
+        .clone()
+        .with_config(KakarotConfig { block_cache_size: 16, ..eth_provider.config().clone() })
+        .unwrap();
+    let first = cached_provider.block_by_hash(*BLOCK_HASH, true).await.unwrap().unwrap();
+
+    // When
+    // The transactions are removed from the database, the cached block still holds them
+    eth_provider
+        .database()
+        .collection::<StoredTransaction>()
+        .delete_many(mongodb::bson::doc! {}, None)
+        .await
+        .expect("Failed to delete transactions");
+    let cached = cached_provider.block_by_number(BlockNumberOrTag::Number(BLOCK_NUMBER), true).await.unwrap().unwrap();
+    let hashes = cached_provider.block_by_hash(*BLOCK_HASH, false).await.unwrap().unwrap();
+    let uncached = eth_provider.block_by_hash(*BLOCK_HASH, true).await.unwrap().unwrap();
+
+    // Then
+    assert_eq!(cached, first);
+    assert!(matches!(&cached.inner.transactions, BlockTransactions::Full(transactions) if !transactions.is_empty()));
+    assert!(matches!(&hashes.inner.transactions, BlockTransactions::Hashes(hashes) if hashes.is_empty()));
+    assert!(matches!(&uncached.inner.transactions, BlockTransactions::Full(transactions) if transactions.is_empty()));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_transactions_reverted(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let transaction = katana.most_recent_transaction().unwrap();
+    let block_number = BlockNumberOrTag::Number(transaction.block_number.unwrap());
+    eth_provider
+        .database()
+        .collection::<StoredTransactionReceipt>()
+        .update_one(
+            into_filter("receipt.transactionHash", &transaction.hash, HASH_HEX_STRING_LEN),
+            mongodb::bson::doc! {"$set": {"receipt.status": "0x0"}},
+            None,
+        )
+        .await
+        .expect("Failed to revert the receipt");
+    let excluding = (*eth_provider)
+        .clone()
+        .with_config(KakarotConfig { exclude_reverted_transactions: true, ..eth_provider.config().clone() })
+        .unwrap();
+
+    // When
+    let included = eth_provider.block_by_number(block_number, false).await.unwrap().unwrap();
+    let excluded = excluding.block_by_number(block_number, false).await.unwrap().unwrap();
+
+    // Then
+    let BlockTransactions::Hashes(included) = included.inner.transactions else { panic!("Expected hashes") };
+    let BlockTransactions::Hashes(excluded) = excluded.inner.transactions else { panic!("Expected hashes") };
+    assert!(included.contains(&transaction.hash));
+    assert!(!excluded.contains(&transaction.hash));
+    assert_eq!(excluded.len(), included.len() - 1);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -173,6 +351,67 @@ async fn test_balance(#[future] katana: Katana, _setup: ()) {
     assert!(eoa_balance > U256::ZERO);
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_balance_fee_token_decimals(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let address = katana.eoa().evm_address().unwrap();
+    let six_decimals = (*eth_provider)
+        .clone()
+        .with_config(KakarotConfig { fee_token_decimals: 6, ..eth_provider.config().clone() })
+        .unwrap();
+
+    // When
+    let balance = eth_provider.balance(address, None).await.unwrap();
+    let six_decimals_balance = six_decimals.balance(address, None).await.unwrap();
+
+    // Then
+    assert_eq!(six_decimals_balance, balance * U256::from(1_000_000_000_000_u64));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_balance_pending(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let eoa = katana.eoa();
+    let sender = eoa.evm_address().unwrap();
+    let recipient = Address::random();
+    let pending = Some(BlockId::Number(BlockNumberOrTag::Pending));
+    let balance_before = eth_provider.balance(sender, pending).await.unwrap();
+
+    let transaction = Transaction::Eip1559(TxEip1559 {
+        chain_id: eth_provider.chain_id().await.unwrap().unwrap().to(),
+        nonce: eoa.nonce().await.unwrap().try_into().unwrap(),
+        gas_limit: 21000,
+        to: TxKind::Call(recipient),
+        value: U256::from(1000),
+        max_fee_per_gas: 875_000_000,
+        ..Default::default()
+    });
+    let signature = sign_message(eoa.private_key(), transaction.signature_hash()).unwrap();
+    let transaction_signed = TransactionSigned::from_transaction_and_signature(transaction, signature);
+
+    // When
+    let tx_hash = eth_provider
+        .send_raw_transaction(transaction_signed.envelope_encoded())
+        .await
+        .expect("failed to send transaction");
+    let starknet_tx_hash = FieldElement::from_bytes_be(&tx_hash.0).unwrap();
+    watch_tx(eth_provider.starknet_provider(), starknet_tx_hash, std::time::Duration::from_millis(300), 60)
+        .await
+        .expect("Tx polling failed");
+
+    // Then
+    let sender_balance = eth_provider.balance(sender, pending).await.unwrap();
+    let recipient_balance = eth_provider.balance(recipient, pending).await.unwrap();
+    assert!(sender_balance <= balance_before - U256::from(1000));
+    assert_eq!(recipient_balance, U256::from(1000));
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -246,6 +485,30 @@ async fn test_nonce(#[future] counter: (Katana, KakarotEvmContract), _setup: ())
     assert_eq!(nonce_before + U256::from(1), nonce_after);
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nonce_pending(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let eoa = katana.eoa();
+    let address = eoa.evm_address().unwrap();
+    let latest = Some(BlockId::Number(BlockNumberOrTag::Latest));
+    let pending = Some(BlockId::Number(BlockNumberOrTag::Pending));
+
+    let nonce: u64 = eth_provider.transaction_count(address, latest).await.unwrap().to();
+    let transaction = eoa.mock_transaction_with_nonce(nonce).await.expect("Failed to get mock transaction");
+
+    // When
+    katana.add_pending_transactions_to_database(vec![transaction]).await;
+
+    // Then
+    let latest_nonce = eth_provider.transaction_count(address, latest).await.unwrap();
+    let pending_nonce = eth_provider.transaction_count(address, pending).await.unwrap();
+    assert_eq!(latest_nonce, U256::from(nonce));
+    assert_eq!(pending_nonce, latest_nonce + U256::from(1));
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -268,6 +531,274 @@ async fn test_get_code(#[future] counter: (Katana, KakarotEvmContract), _setup:
     assert_eq!(bytecode, Bytes::from(expected));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_code_hash(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana: Katana = counter.0;
+    let counter = counter.1;
+    let eth_provider = katana.eth_provider();
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address = counter_address.try_into().expect("Failed to convert EVM address");
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+
+    // When
+    let counter_code_hash = eth_provider.code_hash(counter_address, None).await.unwrap();
+    let eoa_code_hash = eth_provider.code_hash(eoa_address, None).await.unwrap();
+
+    // Then
+    let bytecode = eth_provider.get_code(counter_address, None).await.unwrap();
+    assert_ne!(counter_code_hash, KECCAK_EMPTY);
+    assert_eq!(counter_code_hash, keccak256(bytecode));
+    assert_eq!(eoa_code_hash, KECCAK_EMPTY);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_gas_limit(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let gas_limit = 30_000_000;
+    let eth_provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { block_gas_limit: Some(gas_limit), ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // When
+    let first = eth_provider.block_by_number(BlockNumberOrTag::Number(BLOCK_NUMBER), false).await.unwrap().unwrap();
+    let second = eth_provider.block_by_number(BlockNumberOrTag::Earliest, false).await.unwrap().unwrap();
+
+    // Then
+    assert_ne!(first.header.number, second.header.number);
+    assert_eq!(first.header.gas_limit, gas_limit);
+    assert_eq!(second.header.gas_limit, gas_limit);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_zero_gas_limit_is_rejected(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let config = KakarotConfig { block_gas_limit: Some(0), ..eth_provider.config().clone() };
+
+    // When
+    let result = (*eth_provider).clone().with_config(config);
+
+    // Then
+    assert!(result.is_err());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_parent_hash(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let stored = eth_provider.header(&BlockId::Number(BLOCK_NUMBER.into())).await.unwrap().unwrap();
+
+    // When
+    let earliest = eth_provider.block_by_number(BlockNumberOrTag::Earliest, false).await.unwrap().unwrap();
+    let block = eth_provider.block_by_number(BlockNumberOrTag::Number(BLOCK_NUMBER), false).await.unwrap().unwrap();
+
+    // Then
+    assert_eq!(earliest.header.parent_hash, B256::ZERO);
+    assert!(!stored.parent_hash.is_zero());
+    assert_eq!(block.header.parent_hash, stored.parent_hash);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_by_number_earliest_not_found(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig {
+            earliest_block_number: u64::from(u32::MAX),
+            ..katana.eth_provider().config().clone()
+        })
+        .unwrap();
+
+    // When
+    let err = provider.block_by_number(BlockNumberOrTag::Earliest, false).await.unwrap_err();
+
+    // Then
+    assert!(matches!(err, EthApiError::EarliestBlockNotFound(number) if number == u64::from(u32::MAX)));
+    assert!(err.to_string().contains("earliest block"));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_miner(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let coinbase = Address::from_str("0x00000000000000000000000000000000c0ffee00").unwrap();
+    let sequencer_provider = katana.eth_provider();
+    let coinbase_provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { coinbase: Some(coinbase), ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // When
+    let sequencer_block = sequencer_provider.block_by_hash(*BLOCK_HASH, false).await.unwrap().unwrap();
+    let coinbase_block = coinbase_provider.block_by_hash(*BLOCK_HASH, false).await.unwrap().unwrap();
+
+    // Then
+    let stored_header = katana.header_by_hash(*BLOCK_HASH).unwrap();
+    assert_eq!(sequencer_block.header.miner, stored_header.miner);
+    assert_eq!(coinbase_block.header.miner, coinbase);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_pending_block_hash(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let number = u64::from(u32::MAX);
+    let pending_header = reth_rpc_types::Header {
+        number: Some(number),
+        hash: Some(B256::ZERO),
+        parent_hash: B256::repeat_byte(1),
+        timestamp: 1_700_000_000,
+        ..Default::default()
+    };
+    katana.add_transactions_with_header_to_database(vec![], pending_header.clone()).await;
+    let null_provider = katana.eth_provider();
+    let placeholder_provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig {
+            pending_block_hash: PendingBlockHash::Placeholder,
+            ..katana.eth_provider().config().clone()
+        })
+        .unwrap();
+
+    // When
+    let null_block = null_provider.block_by_number(BlockNumberOrTag::Number(number), false).await.unwrap().unwrap();
+    let placeholder_block =
+        placeholder_provider.block_by_number(BlockNumberOrTag::Number(number), false).await.unwrap().unwrap();
+
+    // Then
+    assert!(null_block.header.hash.is_none());
+    assert_eq!(placeholder_block.header.hash, PendingBlockHash::Placeholder.hash(&pending_header));
+    assert!(placeholder_block.header.hash.is_some_and(|hash| !hash.is_zero()));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_pending_block_null_fields(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let number = u64::from(u32::MAX);
+    let pending_header = reth_rpc_types::Header {
+        number: Some(number),
+        hash: Some(B256::ZERO),
+        nonce: Some(B64::ZERO),
+        parent_hash: B256::repeat_byte(1),
+        timestamp: 1_700_000_000,
+        ..Default::default()
+    };
+    katana.add_transactions_with_header_to_database(vec![], pending_header).await;
+
+    // When
+    let pending = eth_provider.block_by_number(BlockNumberOrTag::Number(number), false).await.unwrap().unwrap();
+    let sealed = eth_provider.block_by_number(BlockNumberOrTag::Number(BLOCK_NUMBER), false).await.unwrap().unwrap();
+
+    // Then
+    assert!(pending.header.hash.is_none());
+    assert!(pending.header.number.is_none());
+    assert!(pending.header.nonce.is_none());
+    assert!(sealed.header.hash.is_some());
+    assert!(sealed.header.number.is_some());
+    assert!(sealed.header.nonce.is_some());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_pending_block_endpoints(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let number = eth_provider.block_number().await.unwrap().to::<u64>() + 1;
+    let pending_header = reth_rpc_types::Header {
+        number: Some(number),
+        hash: Some(B256::ZERO),
+        parent_hash: *BLOCK_HASH,
+        timestamp: 1_700_000_000,
+        ..Default::default()
+    };
+    katana.add_transactions_with_header_to_database(vec![], pending_header).await;
+    let pending_id = BlockId::Number(BlockNumberOrTag::Number(number));
+    let pending_filter = Filter {
+        block_option: FilterBlockOption::Range {
+            from_block: Some(BlockNumberOrTag::Pending),
+            to_block: Some(BlockNumberOrTag::Pending),
+        },
+        ..Default::default()
+    };
+
+    // When
+    let receipts = eth_provider.block_receipts(Some(pending_id)).await;
+    let logs = eth_provider.get_logs(pending_filter).await;
+    let fee_history = eth_provider.fee_history(U64::from(1), BlockNumberOrTag::Pending, None).await;
+    let tracer = TracerBuilder::new(eth_provider.clone()).await.unwrap().with_block_id(pending_id).await;
+
+    // Then
+    assert_eq!(receipts.unwrap(), Some(vec![]));
+    assert!(logs.is_ok());
+    assert!(fee_history.is_ok());
+    assert!(matches!(tracer, Err(EthApiError::UnknownBlock)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_pending_dedup(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let FilterChanges::Logs(logs) = eth_provider.get_logs(Filter::default()).await.unwrap() else {
+        panic!("Expected logs")
+    };
+    let sealed = logs[0].clone();
+    let number = eth_provider.block_number().await.unwrap().to::<u64>() + 1;
+    let pending_header = reth_rpc_types::Header {
+        number: Some(number),
+        hash: Some(B256::ZERO),
+        parent_hash: *BLOCK_HASH,
+        timestamp: 1_700_000_000,
+        ..Default::default()
+    };
+    katana.add_transactions_with_header_to_database(vec![], pending_header).await;
+
+    // The transaction of the sealed log is still indexed in the pending block
+    let pending = Log { block_hash: Some(B256::ZERO), block_number: Some(number), ..sealed.clone() };
+    let mut document = mongodb::bson::to_document(&StoredLog::from(pending)).unwrap();
+    document
+        .get_document_mut("log")
+        .unwrap()
+        .insert("blockNumber", format!("0x{number:0width$x}", width = BLOCK_NUMBER_HEX_STRING_LEN));
+    eth_provider
+        .database()
+        .collection::<StoredLog>()
+        .clone_with_type::<mongodb::bson::Document>()
+        .insert_one(document, None)
+        .await
+        .expect("Failed to insert pending log");
+    let filter = Filter::new().from_block(sealed.block_number.unwrap()).to_block(BlockNumberOrTag::Pending);
+
+    // When
+    let logs = filter_logs(filter, eth_provider.clone()).await;
+
+    // Then
+    let transaction_logs: Vec<_> = logs
+        .into_iter()
+        .filter(|log| log.transaction_hash == sealed.transaction_hash && log.log_index == sealed.log_index)
+        .collect();
+    assert_eq!(transaction_logs, vec![sealed]);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -276,19 +807,374 @@ async fn test_get_logs_block_range(#[future] katana: Katana, _setup: ()) {
     let provider = katana.eth_provider();
 
     // When
-    let logs = provider.get_logs(Filter::default()).await.expect("Failed to get logs");
+    let logs = provider.get_logs(Filter::default()).await.expect("Failed to get logs");
+
+    // Then
+    let FilterChanges::Logs(logs) = logs else { panic!("Expected logs") };
+    assert!(!logs.is_empty());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_denied_topics(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let denied_topic = katana.logs_with_min_topics(1)[0].topics()[0];
+    let provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { denied_log_topics: vec![denied_topic], ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // When
+    let FilterChanges::Logs(all) = katana.eth_provider().get_logs(Filter::default()).await.unwrap() else {
+        panic!("Expected logs")
+    };
+    let FilterChanges::Logs(filtered) = provider.get_logs(Filter::default()).await.unwrap() else {
+        panic!("Expected logs")
+    };
+
+    // Then
+    let expected = all.into_iter().filter(|log| log.topics().first() != Some(&denied_topic)).collect::<Vec<_>>();
+    assert_eq!(filtered.len(), expected.len());
+    assert!(filtered.iter().all(|log| log.topics().first() != Some(&denied_topic)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_denied_system_events(#[future] katana: Katana, _setup: ()) {
+    // Given
+    // A fee transfer of Kakarot, denied by default, and a Transfer log in the same block
+    let eth_provider = katana.eth_provider();
+    let template = filter_logs(Filter::default(), eth_provider.clone()).await[0].clone();
+    let block_hash = B256::repeat_byte(0x43);
+    let number = template.block_number.unwrap();
+    let fee_transfer = event_topic(KAKAROT_SYSTEM_EVENTS[0]);
+    let transfer = event_topic("Transfer(address,address,uint256)");
+    for (log_index, topic) in [(0, fee_transfer), (1, transfer)] {
+        let log = Log {
+            inner: reth_primitives::Log::new_unchecked(template.address(), vec![topic], Bytes::default()),
+            block_hash: Some(block_hash),
+            log_index: Some(log_index),
+            ..template.clone()
+        };
+        let mut document = mongodb::bson::to_document(&StoredLog::from(log)).unwrap();
+        document
+            .get_document_mut("log")
+            .unwrap()
+            .insert("blockNumber", format!("0x{number:0width$x}", width = BLOCK_NUMBER_HEX_STRING_LEN));
+        eth_provider
+            .database()
+            .collection::<StoredLog>()
+            .clone_with_type::<mongodb::bson::Document>()
+            .insert_one(document, None)
+            .await
+            .expect("Failed to insert log");
+    }
+
+    // When
+    let logs = filter_logs(Filter::new().at_block_hash(block_hash), eth_provider.clone()).await;
+
+    // Then
+    assert_eq!(logs.iter().map(|log| log.topics()[0]).collect::<Vec<_>>(), vec![transfer]);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_limit(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let logs = filter_logs(Filter::default(), katana.eth_provider()).await;
+    let count = logs.len() as u64;
+
+    // When
+    let under_limit = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { max_logs: Some(count), ..katana.eth_provider().config().clone() })
+        .unwrap();
+    let over_limit = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { max_logs: Some(count - 1), ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // Then
+    let FilterChanges::Logs(logs) = under_limit.get_logs(Filter::default()).await.unwrap() else {
+        panic!("Expected logs")
+    };
+    assert_eq!(logs.len() as u64, count);
+    let err = over_limit.get_logs(Filter::default()).await.unwrap_err();
+    assert!(matches!(err, EthApiError::QueryLimitExceeded(max) if max == count - 1));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_paginated(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = katana.eth_provider();
+    let mut expected = filter_logs(Filter::default(), provider.clone()).await;
+    expected.sort_by_key(|log| (log.block_number, log.log_index));
+
+    // When
+    let mut logs = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = provider.get_logs_paginated(Filter::default(), cursor, 2).await.expect("Failed to get logs");
+        assert!(page.logs.len() <= 2);
+        logs.extend(page.logs);
+        cursor = page.cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    // Then
+    assert_eq!(logs, expected);
+    let err = provider.get_logs_paginated(Filter::default(), None, 0).await.unwrap_err();
+    assert!(matches!(err, EthApiError::InvalidParams(_)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_paginated_sorts_log_indexes_numerically(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let template = filter_logs(Filter::default(), eth_provider.clone()).await[0].clone();
+    let block_hash = B256::repeat_byte(0x42);
+    let number = template.block_number.unwrap();
+    // Log indexes of one and two hex digits, inserted in reverse order
+    for log_index in (0..20).rev() {
+        let log = Log { block_hash: Some(block_hash), log_index: Some(log_index), ..template.clone() };
+        let mut document = mongodb::bson::to_document(&StoredLog::from(log)).unwrap();
+        document
+            .get_document_mut("log")
+            .unwrap()
+            .insert("blockNumber", format!("0x{number:0width$x}", width = BLOCK_NUMBER_HEX_STRING_LEN));
+        eth_provider
+            .database()
+            .collection::<StoredLog>()
+            .clone_with_type::<mongodb::bson::Document>()
+            .insert_one(document, None)
+            .await
+            .expect("Failed to insert log");
+    }
+
+    // When
+    let mut log_indexes = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = eth_provider
+            .get_logs_paginated(Filter::new().at_block_hash(block_hash), cursor, 3)
+            .await
+            .expect("Failed to get logs");
+        log_indexes.extend(page.logs.iter().map(|log| log.log_index.unwrap()));
+        cursor = page.cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    // Then
+    assert_eq!(log_indexes, (0..20).collect::<Vec<_>>());
+}
+
+async fn filter_logs(filter: Filter, provider: Arc<dyn EthereumProvider>) -> Vec<Log> {
+    let logs = provider.get_logs(filter).await.expect("Failed to get logs");
+    match logs {
+        FilterChanges::Logs(logs) => logs,
+        _ => panic!("Expected logs"),
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_bloom_filter(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let topic = katana.logs_with_min_topics(1)[0].topics()[0];
+    let filter = Filter::new().event_signature(topic);
+    let eth_provider = katana.eth_provider();
+    let bloom_provider = (*eth_provider)
+        .clone()
+        .with_config(KakarotConfig { logs_bloom_filter: true, ..eth_provider.config().clone() })
+        .unwrap();
+
+    // The header blooms are filled from the logs of their block
+    let mut blocks_logs: HashMap<u64, Vec<Log>> = HashMap::new();
+    for log in filter_logs(Filter::default(), eth_provider.clone()).await {
+        blocks_logs.entry(log.block_number.unwrap()).or_default().push(log);
+    }
+    for (number, logs) in blocks_logs {
+        let bloom = logs_bloom(logs.iter().map(|log| &log.inner));
+        let number = format!("0x{number:0width$x}", width = BLOCK_NUMBER_HEX_STRING_LEN);
+        eth_provider
+            .database()
+            .collection::<StoredHeader>()
+            .update_one(
+                mongodb::bson::doc! {"header.number": number},
+                mongodb::bson::doc! {"$set": {"header.logsBloom": format!("{bloom:#x}")}},
+                None,
+            )
+            .await
+            .expect("Failed to update header bloom");
+    }
+
+    // When
+    let bloom_provider = Arc::new(bloom_provider);
+    let mut all = filter_logs(filter.clone(), eth_provider.clone()).await;
+    let mut filtered = filter_logs(filter, bloom_provider.clone()).await;
+    let missing = bloom_provider.get_logs(Filter::new().event_signature(B256::repeat_byte(0xab))).await.unwrap();
+
+    // Then
+    // No block whose logs match the topic is skipped
+    all.sort_by_key(|log| (log.block_number, log.log_index));
+    filtered.sort_by_key(|log| (log.block_number, log.log_index));
+    assert!(!all.is_empty());
+    assert_eq!(filtered, all);
+    assert!(match missing {
+        FilterChanges::Logs(logs) => logs.is_empty(),
+        FilterChanges::Empty => true,
+        _ => false,
+    });
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_block_tags(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = katana.eth_provider();
+    let latest = provider.block_number().await.unwrap().to::<u64>();
+    let range = |from: BlockNumberOrTag, to: BlockNumberOrTag| Filter::new().from_block(from).to_block(to);
+
+    for (tagged, numeric) in [
+        (range(BlockNumberOrTag::Latest, BlockNumberOrTag::Latest), range(latest.into(), latest.into())),
+        (range(BlockNumberOrTag::Earliest, BlockNumberOrTag::Latest), range(0.into(), latest.into())),
+        (range(BlockNumberOrTag::Earliest, BlockNumberOrTag::Safe), range(0.into(), latest.into())),
+        (range(BlockNumberOrTag::Finalized, BlockNumberOrTag::Latest), range(latest.into(), latest.into())),
+    ] {
+        // When
+        let tagged_logs = filter_logs(tagged.clone(), provider.clone()).await;
+        let numeric_logs = filter_logs(numeric, provider.clone()).await;
+
+        // Then
+        assert_eq!(tagged_logs, numeric_logs, "filter {tagged:?}");
+    }
+
+    // The latest block alone only contains the logs of the latest block
+    let latest_logs = filter_logs(range(BlockNumberOrTag::Latest, BlockNumberOrTag::Latest), provider.clone()).await;
+    assert!(latest_logs.iter().all(|log| log.block_number == Some(latest)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_inverted_range(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = katana.eth_provider();
+    let latest = provider.block_number().await.unwrap().to::<u64>();
+    let numeric = Filter::new().from_block(latest).to_block(latest - 1);
+    let tagged = Filter::new().from_block(BlockNumberOrTag::Latest).to_block(BlockNumberOrTag::Earliest);
+
+    // When
+    let numeric = provider.get_logs(numeric).await;
+    let tagged = provider.get_logs(tagged).await;
+
+    // Then
+    assert!(matches!(numeric, Err(EthApiError::InvalidBlockRange)));
+    assert!(matches!(tagged, Err(EthApiError::InvalidBlockRange)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_from_block_past_head(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = katana.eth_provider();
+    let latest = provider.block_number().await.unwrap().to::<u64>();
+    let filter = Filter::new().from_block(latest + 10);
+
+    // When
+    let logs = provider.get_logs(filter).await;
+
+    // Then
+    assert!(matches!(logs, Ok(FilterChanges::Empty)));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "log-index")]
+async fn test_get_logs_index(#[future] katana: Katana, _setup: ()) {
+    use kakarot_rpc::eth_provider::database::types::log::StoredLog;
+
+    // Given
+    let provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { log_index_blocks: 16, ..katana.eth_provider().config().clone() })
+        .unwrap();
+    let latest = provider.block_number().await.unwrap().to::<u64>();
+    let filter = Filter::new().from_block(latest.saturating_sub(3)).to_block(latest);
+    let FilterChanges::Logs(first) = provider.get_logs(filter.clone()).await.unwrap() else { panic!("Expected logs") };
+
+    // When
+    // The logs are removed from the database, the second query can only be served by the index
+    provider
+        .database()
+        .collection::<StoredLog>()
+        .delete_many(mongodb::bson::doc! {}, None)
+        .await
+        .expect("Failed to delete logs");
+    let FilterChanges::Logs(second) = provider.get_logs(filter).await.unwrap() else { panic!("Expected logs") };
+
+    // Then
+    let mut expected = first;
+    expected.sort_by_key(|log| (log.block_number, log.log_index));
+    assert_eq!(second, expected);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "log-index")]
+async fn test_get_logs_index_reorg(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { log_index_blocks: 16, ..katana.eth_provider().config().clone() })
+        .unwrap();
+    let latest = provider.block_number().await.unwrap().to::<u64>();
+    let filter = Filter::new().from_block(latest.saturating_sub(3)).to_block(latest);
+    let FilterChanges::Logs(first) = provider.get_logs(filter.clone()).await.unwrap() else { panic!("Expected logs") };
+
+    // When
+    // The latest block is replaced, its logs being gone along with the logs of the other blocks
+    let number = format!("0x{latest:0width$x}", width = BLOCK_NUMBER_HEX_STRING_LEN);
+    let hash = format!("{:#x}", B256::repeat_byte(0xee));
+    provider
+        .database()
+        .collection::<StoredHeader>()
+        .update_one(
+            mongodb::bson::doc! {"header.number": number},
+            mongodb::bson::doc! {"$set": {"header.hash": hash}},
+            None,
+        )
+        .await
+        .expect("Failed to update header hash");
+    provider
+        .database()
+        .collection::<StoredLog>()
+        .delete_many(mongodb::bson::doc! {}, None)
+        .await
+        .expect("Failed to delete logs");
+    let FilterChanges::Logs(second) = provider.get_logs(filter).await.unwrap() else { panic!("Expected logs") };
 
     // Then
-    let FilterChanges::Logs(logs) = logs else { panic!("Expected logs") };
-    assert!(!logs.is_empty());
-}
-
-async fn filter_logs(filter: Filter, provider: Arc<dyn EthereumProvider>) -> Vec<Log> {
-    let logs = provider.get_logs(filter).await.expect("Failed to get logs");
-    match logs {
-        FilterChanges::Logs(logs) => logs,
-        _ => panic!("Expected logs"),
-    }
+    // Only the replaced block is indexed again
+    let mut expected: Vec<_> = first.into_iter().filter(|log| log.block_number != Some(latest)).collect();
+    expected.sort_by_key(|log| (log.block_number, log.log_index));
+    assert_eq!(second, expected);
 }
 
 #[rstest]
@@ -363,6 +1249,9 @@ async fn test_get_logs_block_hash(#[future] katana: Katana, _setup: ()) {
     let filtered_logs = filter_logs(filter, provider.clone()).await;
 
     assert!(filtered_logs.iter().all(|log| log.block_hash.unwrap() == block_hash));
+    // Only the logs of the block are returned, whatever its position in the chain
+    let block_logs = logs.iter().filter(|log| log.block_hash == Some(block_hash)).count();
+    assert_eq!(filtered_logs.len(), block_logs);
 }
 
 #[rstest]
@@ -425,6 +1314,163 @@ async fn test_estimate_gas(#[future] counter: (Katana, KakarotEvmContract), _set
     assert!(estimate > U256::from(0));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_gas_revert_reason(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let eoa = counter.0.eoa();
+    let eth_provider = counter.0.eth_provider();
+    let counter = counter.1;
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+
+    // Decrementing the counter always reverts while its count is zero
+    let request = TransactionRequest {
+        from: Some(eoa.evm_address().unwrap()),
+        to: Some(TxKind::Call(counter_address.try_into().unwrap())),
+        input: TransactionInput { input: None, data: Some(Bytes::from_str("0xb3bcfa82").unwrap()) }, // selector of "function dec()"
+        ..Default::default()
+    };
+
+    // When
+    let estimate = eth_provider.estimate_gas(request.clone(), None).await.unwrap_err();
+    let call = eth_provider.call(request, None).await.unwrap_err();
+
+    // Then
+    let EthApiError::Kakarot(KakarotError::ExecutionError(EvmError::Other(reason))) = estimate else {
+        panic!("Expected a revert, got {estimate:?}")
+    };
+    assert!(!reason.is_empty());
+    assert!(!reason.starts_with("0x"), "undecoded revert reason {reason}");
+    // The estimate carries the same revert reason as the call
+    assert_eq!(format!("{call:?}"), format!("{:?}", EthApiError::from(KakarotError::from(EvmError::Other(reason)))));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_conflicting_fee_fields(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let eoa = counter.0.eoa();
+    let eth_provider = counter.0.eth_provider();
+    let counter = counter.1;
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+
+    let request = TransactionRequest {
+        from: Some(eoa.evm_address().unwrap()),
+        to: Some(TxKind::Call(counter_address.try_into().unwrap())),
+        input: TransactionInput { input: None, data: Some(Bytes::from_str("0x06661abd").unwrap()) }, // selector of "function count()"
+        gas_price: Some(1_000_000_000),
+        max_fee_per_gas: Some(1_000_000_000),
+        ..Default::default()
+    };
+
+    // When
+    let call = eth_provider.call(request.clone(), None).await;
+    let estimate = eth_provider.estimate_gas(request, None).await;
+
+    // Then
+    for result in [call.map(|_| ()), estimate.map(|_| ())] {
+        let err = result.unwrap_err();
+        assert!(matches!(err, EthApiError::Transaction(TransactionError::ConflictingFeeFields)));
+        assert_eq!(
+            err.to_string(),
+            "transaction error: both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified"
+        );
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_default_gas_limit(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let eoa = counter.0.eoa();
+    let eth_provider = counter.0.eth_provider();
+    let counter = counter.1;
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+
+    let request = TransactionRequest {
+        from: Some(eoa.evm_address().unwrap()),
+        to: Some(TxKind::Call(counter_address.try_into().unwrap())),
+        input: TransactionInput { input: None, data: Some(Bytes::from_str("0x06661abd").unwrap()) }, // selector of "function count()"
+        ..Default::default()
+    };
+
+    // When
+    let unspecified_gas = eth_provider.call(request.clone(), None).await;
+    let zero_gas = eth_provider.call(TransactionRequest { gas: Some(0), ..request }, None).await;
+
+    // Then
+    assert_eq!(unspecified_gas.unwrap().len(), 32);
+    assert!(zero_gas.is_err());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_without_from(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let eth_provider = counter.0.eth_provider();
+    let counter = counter.1;
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+
+    let request = TransactionRequest {
+        to: Some(TxKind::Call(counter_address.try_into().unwrap())),
+        input: TransactionInput { input: None, data: Some(Bytes::from_str("0x06661abd").unwrap()) }, // selector of "function count()"
+        ..Default::default()
+    };
+
+    // When
+    let count = eth_provider.call(request, None).await.unwrap();
+
+    // Then
+    assert_eq!(U256::from_be_slice(&count), U256::ZERO);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_min_gas_price(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let min_gas_price = u128::from(u64::MAX);
+    let provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { min_gas_price, ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // When
+    let gas_price = provider.gas_price().await.unwrap();
+    let max_priority_fee_per_gas = provider.max_priority_fee_per_gas().await.unwrap();
+
+    // Then
+    // The base fee of the quiet test chain is below the floor
+    assert_eq!(gas_price, U256::from(min_gas_price));
+    assert_eq!(max_priority_fee_per_gas, U256::from(min_gas_price));
+    assert!(katana.eth_provider().gas_price().await.unwrap() < U256::from(min_gas_price));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_stream(#[future] katana: Katana, _setup: ()) {
+    use futures::StreamExt as _;
+    use kakarot_rpc::eth_provider::block_stream::block_stream;
+
+    // Given
+    // The mocked headers start with the blocks 0 to 3
+    let header = reth_rpc_types::Header { number: Some(4), hash: Some(B256::repeat_byte(4)), ..Default::default() };
+    katana.add_transactions_with_header_to_database(vec![], header).await;
+
+    // When
+    let blocks: Vec<_> = block_stream(katana.eth_provider(), 0, 2, None).take(5).collect().await;
+
+    // Then
+    let numbers: Vec<_> =
+        blocks.into_iter().map(|block| block.expect("Failed to stream block").header.number).collect();
+    assert_eq!(numbers, (0..5).map(Some).collect::<Vec<_>>());
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -457,6 +1503,56 @@ async fn test_fee_history(#[future] katana: Katana, _setup: ()) {
     assert_eq!(fee_history.oldest_block, 0);
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fee_history_earliest_block(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let newest_block = katana.most_recent_transaction().unwrap().block_number.unwrap();
+    let earliest_block = newest_block - 1;
+    let eth_provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { earliest_block_number: earliest_block, ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // When
+    let fee_history = eth_provider.fee_history(U64::MAX, BlockNumberOrTag::Number(newest_block), None).await.unwrap();
+
+    // Then
+    // More blocks than available are requested, the history starts at the earliest block
+    assert_eq!(fee_history.oldest_block, earliest_block);
+    assert_eq!(fee_history.gas_used_ratio.len(), 2);
+    assert_eq!(fee_history.base_fee_per_gas.len(), 3);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fee_history_pending(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let latest = eth_provider.block_number().await.unwrap().to::<u64>();
+    let pending_header = reth_rpc_types::Header {
+        number: Some(latest + 1),
+        hash: Some(B256::ZERO),
+        gas_used: 50,
+        gas_limit: 100,
+        ..Default::default()
+    };
+    katana.add_transactions_with_header_to_database(vec![], pending_header).await;
+
+    // When
+    let fee_history = eth_provider.fee_history(U64::from(2), BlockNumberOrTag::Pending, None).await.unwrap();
+    let from_genesis = eth_provider.fee_history(U64::MAX, BlockNumberOrTag::Pending, None).await.unwrap();
+
+    // Then
+    assert_eq!(fee_history.oldest_block, latest);
+    assert_eq!(fee_history.gas_used_ratio.len(), 2);
+    assert!((fee_history.gas_used_ratio[1] - 0.5).abs() < f64::EPSILON);
+    assert_eq!(from_genesis.oldest_block, 0);
+    assert!((from_genesis.gas_used_ratio.last().unwrap() - 0.5).abs() < f64::EPSILON);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -504,6 +1600,24 @@ async fn test_predeploy_eoa(#[future] katana: Katana, _setup: ()) {
     assert_eq!(balance_after, balance_before - U256::from(1));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transaction_receipt_type(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+
+    for (hash, tx_type) in [(*LEGACY_TX_HASH, TxType::Legacy), (*EIP1599_TX_HASH, TxType::Eip1559)] {
+        // When
+        let receipt = eth_provider.transaction_receipt(hash).await.unwrap().expect("Missing receipt");
+
+        // Then
+        assert_eq!(u8::from(receipt.transaction_type()), tx_type as u8);
+        let receipt = serde_json::to_value(receipt).expect("Failed to serialize receipt");
+        assert_eq!(receipt["type"], format!("{:#x}", tx_type as u8));
+    }
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -546,6 +1660,58 @@ async fn test_block_receipts(#[future] katana: Katana, _setup: ()) {
     assert!(receipts.is_none());
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receipts_gas_used_approximation(#[future] katana: Katana, _setup: ()) {
+    // Given
+    // A receipt whose gas wasn't traced, which is approximated from the fee of its Starknet transaction
+    let eth_provider = katana.eth_provider();
+    let transaction = katana.most_recent_transaction().unwrap();
+    eth_provider
+        .database()
+        .collection::<StoredTransactionReceipt>()
+        .update_one(
+            into_filter("receipt.transactionHash", &transaction.hash, HASH_HEX_STRING_LEN),
+            mongodb::bson::doc! {"$set": {"receipt.gasUsed": "0x0", "receipt.cumulativeGasUsed": "0x0"}},
+            None,
+        )
+        .await
+        .expect("Failed to reset the gas used of the receipt");
+
+    // When
+    let block_id = reth_rpc_types::BlockId::Hash(RpcBlockHash::from(transaction.block_hash.unwrap()));
+    let block_receipts = eth_provider.block_receipts(Some(block_id)).await.unwrap().unwrap();
+    let receipt = eth_provider.transaction_receipt(transaction.hash).await.unwrap().unwrap();
+
+    // Then
+    assert_eq!(block_receipts, vec![receipt.clone()]);
+    assert_ne!(receipt.gas_used, 0);
+    assert_eq!(receipt.inner.cumulative_gas_used(), receipt.gas_used);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receipts_cumulative_gas_used(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let block_id = reth_rpc_types::BlockId::Number(BlockNumberOrTag::Number(BLOCK_NUMBER));
+
+    // When
+    let block_receipts = eth_provider.block_receipts(Some(block_id)).await.unwrap().unwrap();
+
+    // Then
+    assert!(block_receipts.len() > 1);
+    let mut cumulative_gas_used = 0_u128;
+    for block_receipt in block_receipts {
+        cumulative_gas_used = cumulative_gas_used.saturating_add(block_receipt.gas_used);
+        assert_eq!(block_receipt.inner.cumulative_gas_used(), cumulative_gas_used);
+        let receipt = eth_provider.transaction_receipt(block_receipt.transaction_hash).await.unwrap().unwrap();
+        assert_eq!(receipt, block_receipt);
+    }
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -587,6 +1753,52 @@ async fn test_to_starknet_block_id(#[future] katana: Katana, _setup: ()) {
     assert!(unknown_starknet_block_number.is_err());
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transaction_hashes_round_trip(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let chain_id = eth_provider.chain_id().await.unwrap_or_default().unwrap_or_default().to();
+    let transaction = Transaction::Eip1559(TxEip1559 {
+        chain_id,
+        nonce: 0,
+        gas_limit: 21000,
+        to: TxKind::Call(Address::random()),
+        value: U256::from(1000),
+        max_fee_per_gas: 875_000_000,
+        ..Default::default()
+    });
+    let signature = sign_message(katana.eoa().private_key(), transaction.signature_hash()).unwrap();
+    let transaction_signed = TransactionSigned::from_transaction_and_signature(transaction, signature);
+    let eth_hash = transaction_signed.hash;
+
+    // The testing feature makes the provider return the Starknet hash of the transaction
+    let starknet_hash = eth_provider
+        .send_raw_transaction(transaction_signed.envelope_encoded())
+        .await
+        .expect("failed to send transaction");
+    let starknet_hash = FieldElement::from_bytes_be(&starknet_hash.0).unwrap();
+
+    // When
+    let resolved_starknet_hash = eth_provider.starknet_transaction_hash(eth_hash).await.unwrap();
+    let resolved_eth_hash = eth_provider.eth_hash_for_starknet(starknet_hash).await.unwrap();
+
+    // When: the stored hashes are removed, the Ethereum hash is recovered from the Starknet transaction
+    eth_provider
+        .database()
+        .delete_one::<StoredStarknetTransactionHash>(into_filter("ethHash", &eth_hash, HASH_HEX_STRING_LEN))
+        .await
+        .unwrap();
+    let recovered_eth_hash = eth_provider.eth_hash_for_starknet(starknet_hash).await.unwrap();
+
+    // Then
+    assert_eq!(resolved_starknet_hash, Some(B256::from_slice(&starknet_hash.to_bytes_be())));
+    assert_eq!(resolved_eth_hash, Some(eth_hash));
+    assert_eq!(recovered_eth_hash, Some(eth_hash));
+    assert_eq!(eth_provider.starknet_transaction_hash(eth_hash).await.unwrap(), None);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -632,6 +1844,46 @@ async fn test_send_raw_transaction(#[future] katana: Katana, _setup: ()) {
     assert!(tx.block_number.is_none());
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_send_raw_transaction_sender_allowlist(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eoa = katana.eoa();
+    let sender = eoa.evm_address().unwrap();
+    let chain_id = katana.eth_provider().chain_id().await.unwrap_or_default().unwrap_or_default().to();
+    let transaction = Transaction::Eip1559(TxEip1559 {
+        chain_id,
+        gas_limit: 21000,
+        to: TxKind::Call(Address::random()),
+        value: U256::from(1000),
+        max_fee_per_gas: 875_000_000,
+        ..Default::default()
+    });
+    let signature = sign_message(eoa.private_key(), transaction.signature_hash()).unwrap();
+    let transaction_signed = TransactionSigned::from_transaction_and_signature(transaction, signature);
+
+    let allowed = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { sender_allowlist: Some(vec![sender]), ..katana.eth_provider().config().clone() })
+        .unwrap();
+    let rejected = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig {
+            sender_allowlist: Some(vec![Address::random()]),
+            ..katana.eth_provider().config().clone()
+        })
+        .unwrap();
+
+    // When
+    let rejected = rejected.send_raw_transaction(transaction_signed.envelope_encoded()).await;
+    let allowed = allowed.send_raw_transaction(transaction_signed.envelope_encoded()).await;
+
+    // Then
+    assert!(matches!(rejected, Err(EthApiError::Unauthorized(address)) if address == sender));
+    assert!(allowed.is_ok());
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -783,6 +2035,47 @@ async fn test_transaction_by_hash(#[future] katana: Katana, _setup: ()) {
     assert_eq!(eth_provider.transaction_by_hash(tx.hash).await.unwrap().unwrap().block_number, Some(1111));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_transaction_by_hash_scan(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = counter.0;
+    let counter = counter.1;
+    let eoa = katana.eoa();
+    let eth_provider = (*katana.eth_provider())
+        .clone()
+        .with_config(KakarotConfig { transaction_scan_depth: 3, ..katana.eth_provider().config().clone() })
+        .unwrap();
+
+    // Each transaction is mined in its own block
+    let mut hashes = Vec::new();
+    for _ in 0..4 {
+        let transaction = eoa.call_evm_contract(&counter, "inc", (), 0).await.expect("Failed to increment counter");
+        hashes.push(eoa.sign_transaction(transaction).unwrap().hash);
+    }
+    // The transactions are not indexed, only stored as pending transactions by the provider
+    eth_provider
+        .database()
+        .collection::<StoredPendingTransaction>()
+        .delete_many(mongodb::bson::doc! {}, None)
+        .await
+        .expect("Failed to delete pending transactions");
+
+    // When
+    let in_window = eth_provider.transaction_by_hash(hashes[1]).await.unwrap();
+    let beyond_window = eth_provider.transaction_by_hash(hashes[0]).await.unwrap();
+
+    // Then
+    // The second transaction is in the third most recent block
+    let in_window = in_window.expect("Failed to find the transaction within the scanned blocks");
+    assert_eq!(in_window.hash, hashes[1]);
+    assert!(in_window.block_hash.is_some());
+    assert_eq!(in_window.transaction_index, Some(0));
+    assert!(beyond_window.is_none());
+    assert!(katana.eth_provider().transaction_by_hash(hashes[1]).await.unwrap().is_none());
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -0,0 +1,117 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use kakarot_rpc::eth_provider::provider::EthereumProvider;
+use kakarot_rpc::eth_rpc::ipc::run_ipc_server;
+use kakarot_rpc::eth_rpc::rpc::KakarotRpcModuleBuilder;
+use kakarot_rpc::test_utils::fixtures::{katana, setup};
+use kakarot_rpc::test_utils::katana::Katana;
+use kakarot_rpc::test_utils::rpc::RawRpcParamsBuilder;
+use reth_primitives::U64;
+use rstest::*;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::sync::Notify;
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ipc_chain_id(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let path = std::env::temp_dir().join(format!("kakarot-rpc-{}.ipc", std::process::id()));
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let handle = run_ipc_server(module, &path, None).expect("Failed to start IPC server");
+    let expected = katana.eth_provider().chain_id().await.expect("Failed to get chain id").unwrap();
+
+    // When
+    let raw = ipc_request(&path, &RawRpcParamsBuilder::new("eth_chainId").build()).await;
+
+    // Then
+    let chain_id: U64 = serde_json::from_value(raw["result"].clone()).expect("Failed to deserialize result");
+    assert_eq!(chain_id, expected);
+
+    // The socket file is removed on shutdown
+    assert!(handle.stop(Duration::from_secs(10)).await);
+    assert!(!path.exists());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ipc_invalid_and_batch_requests(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let path = std::env::temp_dir().join(format!("kakarot-rpc-batch-{}.ipc", std::process::id()));
+    let module = KakarotRpcModuleBuilder::new(katana.eth_provider()).rpc_module().expect("Failed to build RPC module");
+    let handle = run_ipc_server(module, &path, Some(2)).expect("Failed to start IPC server");
+    let chain_id = RawRpcParamsBuilder::new("eth_chainId").set_id(1).build();
+    let block_number = RawRpcParamsBuilder::new("eth_blockNumber").set_id(2).build();
+
+    // When
+    let malformed = ipc_request(&path, "{\"jsonrpc\": \"2.0\",").await;
+    let invalid = ipc_request(&path, "{\"jsonrpc\": \"2.0\", \"id\": 1}").await;
+    let batch = ipc_request(&path, &format!("[{chain_id},{block_number}]")).await;
+    let too_large = ipc_request(&path, &format!("[{chain_id},{block_number},{chain_id}]")).await;
+
+    // Then
+    assert_eq!(malformed["error"]["code"], -32700);
+    assert_eq!(invalid["error"]["code"], -32600);
+    assert_eq!(invalid["id"], 1);
+    let batch = batch.as_array().expect("Expected a batch response");
+    assert_eq!(batch.len(), 2);
+    assert!(batch.iter().all(|response| response.get("result").is_some()));
+    assert_eq!(too_large["error"]["code"], -32010);
+
+    handle.stop(Duration::from_secs(10)).await;
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ipc_stop_drains_in_flight_requests(_setup: ()) {
+    // Given
+    // A method which is slow on purpose, notifying when it started handling the call
+    let started = Arc::new(Notify::new());
+    let mut module = RpcModule::new(());
+    let notify = started.clone();
+    module
+        .register_async_method("test_slow", move |_, _| {
+            let started = notify.clone();
+            async move {
+                started.notify_one();
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                Ok::<_, ErrorObjectOwned>("done".to_string())
+            }
+        })
+        .expect("Failed to register the slow method");
+    let path = std::env::temp_dir().join(format!("kakarot-rpc-drain-{}.ipc", std::process::id()));
+    let handle = run_ipc_server(module, &path, None).expect("Failed to start IPC server");
+    let request_path = path.clone();
+    let in_flight =
+        tokio::spawn(async move { ipc_request(&request_path, &RawRpcParamsBuilder::new("test_slow").build()).await });
+    started.notified().await;
+
+    // When
+    let still_running = !in_flight.is_finished();
+    let drained = handle.stop(Duration::from_secs(10)).await;
+
+    // Then
+    assert!(still_running);
+    assert!(drained);
+    let response = in_flight.await.expect("In-flight request failed");
+    assert_eq!(response["result"], json!("done"));
+    assert!(!path.exists());
+}
+
+/// Sends a request over a new connection to the IPC server and returns its response.
+async fn ipc_request(path: &Path, body: &str) -> Value {
+    let mut stream = UnixStream::connect(path).await.expect("Failed to connect to IPC server");
+    stream.write_all(format!("{body}\n").as_bytes()).await.expect("Failed to send request");
+    let mut response = String::new();
+    BufReader::new(&mut stream).read_line(&mut response).await.expect("Failed to read response");
+    serde_json::from_str(&response).expect("Failed to deserialize response body")
+}
@@ -0,0 +1,182 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+use kakarot_rpc::eth_provider::provider::EthereumProvider;
+use kakarot_rpc::eth_rpc::api::kakarot_api::KakarotApiServer;
+use kakarot_rpc::eth_rpc::servers::kakarot_rpc::KakarotRpc;
+use kakarot_rpc::models::felt::Felt252Wrapper;
+use kakarot_rpc::test_utils::eoa::Eoa;
+use kakarot_rpc::test_utils::evm_contract::KakarotEvmContract;
+use kakarot_rpc::test_utils::fixtures::{counter, katana, setup};
+use kakarot_rpc::test_utils::katana::Katana;
+use kakarot_rpc::test_utils::rpc::RawRpcParamsBuilder;
+use reth_primitives::{Address, B256, U256};
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use rstest::*;
+use serde_json::Value;
+use starknet::core::types::BlockId;
+use starknet::providers::Provider;
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_starknet_block(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let module = KakarotRpc::new(eth_provider.clone()).with_debug_endpoints(true).into_rpc();
+    let expected = eth_provider
+        .starknet_provider()
+        .get_block_with_txs(BlockId::Number(0))
+        .await
+        .expect("Failed to get Starknet block");
+
+    // When
+    let (response, _) = module
+        .raw_json_request(&RawRpcParamsBuilder::new("kakarot_getStarknetBlock").add_param("earliest").build(), 1)
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    assert_eq!(response["result"], serde_json::to_value(expected).unwrap());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_starknet_block_disabled(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpc::new(katana.eth_provider()).with_debug_endpoints(false).into_rpc();
+
+    // When
+    let (response, _) = module
+        .raw_json_request(&RawRpcParamsBuilder::new("kakarot_getStarknetBlock").add_param("latest").build(), 1)
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    assert_eq!(response["error"]["code"], -32601);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_storage_at_batch(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let (katana, counter) = counter;
+    let eth_provider = katana.eth_provider();
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address: Address = counter_address.try_into().expect("Failed to convert EVM address");
+    katana.eoa().call_evm_contract(&counter, "inc", (), 0).await.expect("Failed to increment counter");
+    let module = KakarotRpc::new(eth_provider.clone()).into_rpc();
+    let slots = [U256::from(1), U256::ZERO, U256::from(2)];
+
+    // When
+    let (response, _) = module
+        .raw_json_request(
+            &RawRpcParamsBuilder::new("kakarot_getStorageAtBatch")
+                .add_param(counter_address)
+                .add_param(slots)
+                .add_param("latest")
+                .build(),
+            1,
+        )
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    let values: Vec<B256> = serde_json::from_value(response["result"].clone()).expect("Failed to deserialize slots");
+    let mut expected = Vec::new();
+    for slot in slots {
+        expected.push(eth_provider.storage_at(counter_address, JsonStorageKey::from(slot), None).await.unwrap());
+    }
+    assert_eq!(values, expected);
+    assert_eq!(values[1], B256::left_padding_from(&[0x1]));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_storage_at_batch_too_many_slots(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpc::new(katana.eth_provider()).with_max_batch_values(2).into_rpc();
+    let slots = [U256::from(1), U256::ZERO, U256::from(2)];
+
+    // When
+    let (response, _) = module
+        .raw_json_request(
+            &RawRpcParamsBuilder::new("kakarot_getStorageAtBatch")
+                .add_param(Address::ZERO)
+                .add_param(slots)
+                .add_param("latest")
+                .build(),
+            1,
+        )
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["message"], "invalid params: too many storage slots");
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_balances(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let (katana, counter) = counter;
+    let eth_provider = katana.eth_provider();
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get Eoa EVM address");
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address: Address = counter_address.try_into().expect("Failed to convert EVM address");
+    let undeployed_address = Address::repeat_byte(0xde);
+    let addresses = [eoa_address, counter_address, undeployed_address];
+    let module = KakarotRpc::new(eth_provider.clone()).into_rpc();
+
+    // When
+    let (response, _) = module
+        .raw_json_request(
+            &RawRpcParamsBuilder::new("kakarot_getBalances").add_param(addresses).add_param("latest").build(),
+            1,
+        )
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    let balances: Vec<U256> =
+        serde_json::from_value(response["result"].clone()).expect("Failed to deserialize balances");
+    let mut expected = Vec::new();
+    for address in addresses {
+        expected.push(eth_provider.balance(address, None).await.unwrap());
+    }
+    assert_eq!(balances, expected);
+    assert!(balances[0] > U256::ZERO);
+    assert_eq!(balances[2], U256::ZERO);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_balances_too_many_addresses(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let module = KakarotRpc::new(katana.eth_provider()).with_max_batch_values(2).into_rpc();
+    let addresses = [Address::repeat_byte(0x01), Address::repeat_byte(0x02), Address::repeat_byte(0x03)];
+
+    // When
+    let (response, _) = module
+        .raw_json_request(
+            &RawRpcParamsBuilder::new("kakarot_getBalances").add_param(addresses).add_param("latest").build(),
+            1,
+        )
+        .await
+        .expect("Failed to call RPC module");
+
+    // Then
+    let response: Value = serde_json::from_str(&response).expect("Failed to deserialize response");
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["message"], "invalid params: too many addresses");
+}
@@ -1,5 +1,8 @@
 pub mod alchemy_api;
 pub mod debug_api;
+pub mod eth_api;
 pub mod eth_provider;
+pub mod ipc;
+pub mod kakarot_api;
 pub mod trace_api;
 pub mod txpool_api;
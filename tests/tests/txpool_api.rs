@@ -7,6 +7,7 @@ use kakarot_rpc::test_utils::katana::Katana;
 use kakarot_rpc::test_utils::mongo::RANDOM_BYTES_SIZE;
 use kakarot_rpc::test_utils::rpc::start_kakarot_rpc_server;
 use kakarot_rpc::test_utils::rpc::RawRpcParamsBuilder;
+use reth_primitives::Address;
 use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus};
 use rstest::*;
 use serde::de::DeserializeOwned;
@@ -102,6 +103,44 @@ async fn test_txpool_content(#[future] katana: Katana, _setup: ()) {
     drop(server_handle);
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_txpool_content_sorted_by_nonce(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let bytes: Vec<u8> = (0..RANDOM_BYTES_SIZE).map(|_| rand::random()).collect();
+    let mut unstructured = arbitrary::Unstructured::new(&bytes);
+    let sender = StoredPendingTransaction::arbitrary_with_optional_fields(&mut unstructured).unwrap().tx.from;
+    let mut pending_transactions = Vec::new();
+    for nonce in [10, 2, 9, 0, 1] {
+        let mut transaction = StoredPendingTransaction::arbitrary_with_optional_fields(&mut unstructured).unwrap().tx;
+        transaction.from = sender;
+        transaction.nonce = nonce;
+        pending_transactions.push(transaction);
+    }
+    katana.add_pending_transactions_to_database(pending_transactions).await;
+
+    // When
+    let tx_pool_content: Value = request("txpool_content", server_addr.port(), Vec::<String>::new()).await;
+
+    // Then
+    // The order of the keys of the response is kept
+    let (_, bucket) = tx_pool_content["pending"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .find(|(address, _)| address.parse::<Address>().unwrap() == sender)
+        .unwrap();
+    let bucket = bucket.as_object().unwrap();
+    assert_eq!(bucket.keys().collect::<Vec<_>>(), vec!["0", "1", "2", "9", "10"]);
+    let nonces: Vec<_> = bucket.values().map(|transaction| transaction["nonce"].as_str().unwrap()).collect();
+    assert_eq!(nonces, vec!["0x0", "0x1", "0x2", "0x9", "0xa"]);
+
+    drop(server_handle);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]